@@ -0,0 +1,320 @@
+//! Typed messages for values living on an owner thread.
+
+use std::{cell::RefCell, fmt, mem::ManuallyDrop, sync::Arc};
+
+use crate::{Call, Porter, PorterHandle, ThreadBound};
+
+/// A value that lives on its owner thread and is driven by messages.
+///
+/// Use [`define_bound_actor!`](crate::define_bound_actor) to define the message
+/// enum together with the handler.
+pub trait BoundActor: 'static {
+    /// Message accepted by the actor.
+    type Message: Send + 'static;
+    /// Reply produced for each message.
+    type Reply: Send + 'static;
+
+    /// Handles a message on the owner thread.
+    fn handle(&mut self, msg: Self::Message) -> Self::Reply;
+}
+
+struct Inner<A: 'static> {
+    actor: ManuallyDrop<ThreadBound<RefCell<A>>>,
+    porter: PorterHandle,
+}
+
+impl<A: 'static> Drop for Inner<A> {
+    fn drop(&mut self) {
+        let actor = unsafe { ManuallyDrop::take(&mut self.actor) };
+        self.porter.drop_bound(actor);
+    }
+}
+
+/// Sends messages to a [`BoundActor`] living on the thread of a [`Porter`].
+///
+/// The mailbox is [`Send`] + [`Sync`] and can be cloned freely.
+/// The actor is dropped on its owner thread once the last mailbox is gone.
+pub struct Mailbox<A: 'static> {
+    inner: Arc<Inner<A>>,
+}
+
+impl<A> Mailbox<A>
+where
+    A: BoundActor,
+{
+    /// Moves the actor into a mailbox served by the porter of the current thread.
+    pub fn new(porter: &Porter, actor: A) -> Self {
+        let inner =
+            Inner { actor: ManuallyDrop::new(ThreadBound::new(RefCell::new(actor))), porter: porter.handle() };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Sends a message without waiting for the reply.
+    ///
+    /// When called from the owner thread, the message is handled inline,
+    /// unless the actor is currently handling another message, in which case
+    /// it is queued.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    #[track_caller]
+    pub fn send(&self, msg: A::Message) {
+        if self.inner.porter.is_owner() {
+            if let Ok(mut actor) = self.inner.actor.try_borrow_mut() {
+                actor.handle(msg);
+                return;
+            }
+        }
+
+        let inner = self.inner.clone();
+        self.inner.porter.post(move || {
+            inner.actor.borrow_mut().handle(msg);
+        });
+    }
+
+    /// Sends a message and returns the reply.
+    ///
+    /// When called from the owner thread, the message is handled inline
+    /// and the returned future is already resolved.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped or if called from within
+    /// the handler of the actor.
+    #[track_caller]
+    pub fn request(&self, msg: A::Message) -> Call<A::Reply> {
+        let inner = self.inner.clone();
+        self.inner.porter.call(move || {
+            let mut actor =
+                inner.actor.try_borrow_mut().expect("actor cannot handle a request from within its own handler");
+            actor.handle(msg)
+        })
+    }
+}
+
+impl<A: 'static> Clone for Mailbox<A> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<A: 'static> fmt::Debug for Mailbox<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Mailbox").field("thread_id", &self.inner.porter.thread_id()).finish()
+    }
+}
+
+/// Defines the message enum of a [`BoundActor`] together with its handler.
+///
+/// The handler is written like a closure receiving the actor by mutable reference
+/// and the message by value:
+///
+/// ```
+/// # use std::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+/// use std::{rc::Rc, thread};
+/// use threadporter::{define_bound_actor, Mailbox, Porter};
+///
+/// struct Counter {
+///     value: Rc<i64>,
+/// }
+///
+/// define_bound_actor! {
+///     /// Messages understood by the counter.
+///     pub enum CounterMsg for Counter => i64 {
+///         Add(i64),
+///         Get,
+///     }
+///     |counter, msg| match msg {
+///         CounterMsg::Add(n) => { counter.value = Rc::new(*counter.value + n); *counter.value }
+///         CounterMsg::Get => *counter.value,
+///     }
+/// }
+///
+/// let porter = Porter::new();
+/// let mailbox = Mailbox::new(&porter, Counter { value: Rc::new(0) });
+///
+/// let remote = mailbox.clone();
+/// thread::spawn(move || remote.send(CounterMsg::Add(2))).join().unwrap();
+/// porter.run_until_idle();
+///
+/// // Requests from the owner thread are handled inline.
+/// let request = pin!(mailbox.request(CounterMsg::Add(3)));
+/// let Poll::Ready(value) = request.poll(&mut Context::from_waker(Waker::noop())) else { unreachable!() };
+/// assert_eq!(value, 5);
+/// ```
+#[macro_export]
+macro_rules! define_bound_actor {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident for $actor:ty => $reply:ty {
+            $($variants:tt)*
+        }
+        |$this:ident, $msg:ident| $body:expr $(;)?
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variants)*
+        }
+
+        impl $crate::BoundActor for $actor {
+            type Message = $name;
+            type Reply = $reply;
+
+            fn handle(&mut self, msg: $name) -> $reply {
+                let $this = self;
+                let $msg = msg;
+                $body
+            }
+        }
+    };
+}
+
+/// Client of an RPC handler living on its owner thread, used by [`define_bound_rpc!`](crate::define_bound_rpc).
+#[doc(hidden)]
+pub struct RpcClient<H>
+where
+    H: ?Sized + 'static,
+{
+    inner: Arc<Inner<Box<H>>>,
+}
+
+impl<H> RpcClient<H>
+where
+    H: ?Sized + 'static,
+{
+    pub fn new(porter: &Porter, handler: Box<H>) -> Self {
+        let inner =
+            Inner { actor: ManuallyDrop::new(ThreadBound::new(RefCell::new(handler))), porter: porter.handle() };
+        Self { inner: Arc::new(inner) }
+    }
+
+    #[track_caller]
+    pub fn call<R>(&self, f: impl FnOnce(&mut H) -> R + Send + 'static) -> Call<R>
+    where
+        R: Send + 'static,
+    {
+        let inner = self.inner.clone();
+        self.inner.porter.call(move || {
+            let mut handler =
+                inner.actor.try_borrow_mut().expect("handler cannot serve a request from within its own method");
+            f(&mut **handler)
+        })
+    }
+
+    pub fn porter(&self) -> &PorterHandle {
+        &self.inner.porter
+    }
+}
+
+impl<H> Clone for RpcClient<H>
+where
+    H: ?Sized + 'static,
+{
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+/// Defines a handler trait for requests served on the owner thread together with a client
+/// that sends them from any thread.
+///
+/// Each method declares the [`Send`] arguments of a request and its [`Send`] response type,
+/// which defaults to `()`.
+/// The handler trait receives the requests by mutable reference on the owner thread.
+/// The client is [`Send`] + [`Sync`] + [`Clone`], is created from a handler by `new`
+/// and has a method for each request that returns a [`Call`] resolving to the response.
+/// When called from the owner thread, the request is handled inline.
+/// The handler is dropped on its owner thread once the last client is gone.
+///
+/// ```
+/// # use std::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+/// use std::{rc::Rc, thread};
+/// use threadporter::{define_bound_rpc, Porter};
+///
+/// define_bound_rpc! {
+///     /// Requests served by the renderer.
+///     pub trait Renderer, client RendererClient {
+///         /// Resizes the canvas.
+///         fn resize(width: u32, height: u32);
+///         /// The area of the canvas.
+///         fn area() -> u32;
+///     }
+/// }
+///
+/// struct Canvas {
+///     size: Rc<(u32, u32)>,
+/// }
+///
+/// impl Renderer for Canvas {
+///     fn resize(&mut self, width: u32, height: u32) {
+///         self.size = Rc::new((width, height));
+///     }
+///
+///     fn area(&mut self) -> u32 {
+///         self.size.0 * self.size.1
+///     }
+/// }
+///
+/// let porter = Porter::new();
+/// let client = RendererClient::new(&porter, Canvas { size: Rc::new((0, 0)) });
+///
+/// // Requests from the owner thread are handled inline.
+/// let resize = pin!(client.resize(640, 480));
+/// assert!(resize.poll(&mut Context::from_waker(Waker::noop())).is_ready());
+///
+/// let remote = client.clone();
+/// let area = thread::spawn(move || remote.area()).join().unwrap();
+/// porter.run_until_idle();
+/// let Poll::Ready(area) = pin!(area).poll(&mut Context::from_waker(Waker::noop())) else { unreachable!() };
+/// assert_eq!(area, 640 * 480);
+/// ```
+///
+/// ### Panics
+/// The methods of the client panic if the porter has been dropped or if called from
+/// within a method of the handler.
+#[macro_export]
+macro_rules! define_bound_rpc {
+    (
+        $(#[$meta:meta])*
+        $vis:vis trait $handler:ident, client $client:ident {
+            $(
+                $(#[$fn_meta:meta])*
+                fn $method:ident($($arg:ident: $arg_ty:ty),* $(,)?) $(-> $ret:ty)?;
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        $vis trait $handler: 'static {
+            $(
+                $(#[$fn_meta])*
+                fn $method(&mut self, $($arg: $arg_ty),*) -> $crate::define_bound_rpc!(@ret $($ret)?);
+            )*
+        }
+
+        #[doc = concat!("Sends requests to a [`", stringify!($handler), "`] living on its owner thread.")]
+        #[derive(Clone)]
+        $vis struct $client($crate::RpcClient<dyn $handler>);
+
+        impl $client {
+            /// Moves the handler into a client served by the porter of the current thread.
+            $vis fn new(porter: &$crate::Porter, handler: impl $handler) -> Self {
+                Self($crate::RpcClient::new(porter, ::std::boxed::Box::new(handler)))
+            }
+
+            $(
+                $(#[$fn_meta])*
+                #[track_caller]
+                $vis fn $method(&self, $($arg: $arg_ty),*) -> $crate::Call<$crate::define_bound_rpc!(@ret $($ret)?)> {
+                    self.0.call(move |handler| handler.$method($($arg),*))
+                }
+            )*
+        }
+
+        impl ::std::fmt::Debug for $client {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                f.debug_struct(stringify!($client)).field("thread_id", &self.0.porter().thread_id()).finish()
+            }
+        }
+    };
+    (@ret) => { () };
+    (@ret $ret:ty) => { $ret };
+}
@@ -0,0 +1,262 @@
+//! Arena of values sharing one owner thread.
+
+use std::{
+    fmt,
+    sync::atomic::{AtomicU32, Ordering},
+    thread::ThreadId,
+};
+
+use crate::{Panicking, Policy, Strict, ThreadBound, WrongThread};
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+/// An id of a value allocated in a [`BoundArena`].
+///
+/// Ids are [`Copy`] + [`Send`] + [`Sync`] and are only resolved by the arena
+/// that allocated them on its owner thread.
+/// Ids allocated before the arena was [cleared](BoundArena::clear) no longer resolve,
+/// and neither do ids allocated by another arena.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArenaId {
+    arena: u32,
+    index: u32,
+    epoch: u32,
+}
+
+impl fmt::Debug for ArenaId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ArenaId({}e{}a{})", self.index, self.epoch, self.arena)
+    }
+}
+
+struct Values<T> {
+    id: u32,
+    values: Vec<T>,
+    epoch: u32,
+}
+
+impl<T> Values<T> {
+    fn alloc(&mut self, value: T) -> ArenaId {
+        let index = u32::try_from(self.values.len()).expect("arena is full");
+        self.values.push(value);
+        ArenaId { arena: self.id, index, epoch: self.epoch }
+    }
+
+    fn resolves(&self, id: ArenaId) -> bool {
+        id.arena == self.id && id.epoch == self.epoch
+    }
+
+    fn get(&self, id: ArenaId) -> Option<&T> {
+        if !self.resolves(id) {
+            return None;
+        }
+        self.values.get(id.index as usize)
+    }
+
+    fn get_mut(&mut self, id: ArenaId) -> Option<&mut T> {
+        if !self.resolves(id) {
+            return None;
+        }
+        self.values.get_mut(id.index as usize)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (ArenaId, &T)> + '_ {
+        let (arena, epoch) = (self.id, self.epoch);
+        self.values
+            .iter()
+            .enumerate()
+            .map(move |(index, value)| (ArenaId { arena, index: index as u32, epoch }, value))
+    }
+
+    fn clear(&mut self) {
+        self.epoch = self.epoch.wrapping_add(1);
+        self.values.clear();
+    }
+}
+
+/// Many values bound to the same thread with the policy `P`.
+///
+/// Compared to a [`ThreadBound`] per value, the owner thread, the policy and the drop
+/// bookkeeping are stored once for all values, and access is checked once per operation.
+/// Values cannot be removed individually, but are all dropped together by
+/// [`clear`](Self::clear) or when the arena is dropped.
+///
+/// The arena is [`Send`] + [`Sync`], but values can only be allocated and accessed
+/// on the thread that created the arena.
+/// Under policies that do not panic, like [`Lenient`](crate::Lenient), the `try_*` accessors
+/// return a [`WrongThread`] error instead.
+///
+/// ### Panics
+/// Panics if the arena is accessed in any way from another thread, or dropped on
+/// another thread if its values need drop, as determined by the policy `P`.
+pub struct BoundArena<T, P = Strict>
+where
+    P: Policy,
+{
+    values: ThreadBound<Values<T>, P>,
+}
+
+impl<T> BoundArena<T> {
+    /// Creates an empty arena bound to the current thread.
+    #[track_caller]
+    pub fn new() -> Self {
+        Self::with_policy()
+    }
+}
+
+impl<T, P> BoundArena<T, P>
+where
+    P: Policy,
+{
+    /// Creates an empty arena bound to the current thread using the policy `P`.
+    #[track_caller]
+    pub fn with_policy() -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        Self { values: ThreadBound::with_policy(Values { id, values: Vec::new(), epoch: 0 }) }
+    }
+
+    /// The id of the thread that is allowed to access the arena.
+    pub fn thread_id(&self) -> ThreadId {
+        ThreadBound::thread_id(&self.values)
+    }
+
+    /// Whether the arena is usable from the current thread.
+    pub fn is_usable(&self) -> bool {
+        ThreadBound::is_usable(&self.values)
+    }
+
+    /// Allocates the value in the arena and returns its id, if called from the owner thread.
+    ///
+    /// Otherwise, the value is handed back along with the error.
+    #[track_caller]
+    pub fn try_alloc(&mut self, value: T) -> Result<ArenaId, (T, WrongThread)> {
+        match ThreadBound::try_get_mut(&mut self.values) {
+            Ok(values) => Ok(values.alloc(value)),
+            Err(err) => Err((value, err)),
+        }
+    }
+
+    /// The value of the id, if called from the owner thread.
+    ///
+    /// Like [`get`](Self::get), this is `None` if the arena has been cleared since allocating the id
+    /// or the id was allocated by another arena.
+    #[track_caller]
+    pub fn try_get(&self, id: ArenaId) -> Result<Option<&T>, WrongThread> {
+        Ok(ThreadBound::try_get(&self.values)?.get(id))
+    }
+
+    /// The value of the id mutably, if called from the owner thread.
+    ///
+    /// Like [`get_mut`](Self::get_mut), this is `None` if the arena has been cleared since allocating the id
+    /// or the id was allocated by another arena.
+    #[track_caller]
+    pub fn try_get_mut(&mut self, id: ArenaId) -> Result<Option<&mut T>, WrongThread> {
+        Ok(ThreadBound::try_get_mut(&mut self.values)?.get_mut(id))
+    }
+
+    /// Iterates over the ids and values in order of allocation, if called from the owner thread.
+    #[track_caller]
+    pub fn try_iter(&self) -> Result<impl Iterator<Item = (ArenaId, &T)> + '_, WrongThread> {
+        Ok(ThreadBound::try_get(&self.values)?.iter())
+    }
+
+    /// Drops all values in the arena, if called from the owner thread.
+    #[track_caller]
+    pub fn try_clear(&mut self) -> Result<(), WrongThread> {
+        ThreadBound::try_get_mut(&mut self.values)?.clear();
+        Ok(())
+    }
+
+    /// The number of values in the arena, if called from the owner thread.
+    #[track_caller]
+    pub fn try_len(&self) -> Result<usize, WrongThread> {
+        Ok(ThreadBound::try_get(&self.values)?.values.len())
+    }
+}
+
+impl<T, P> BoundArena<T, P>
+where
+    P: Panicking,
+{
+    /// Allocates the value in the arena and returns its id.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn alloc(&mut self, value: T) -> ArenaId {
+        self.values.alloc(value)
+    }
+
+    /// The value of the id, or `None` if the arena has been cleared since allocating it
+    /// or it was allocated by another arena.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn get(&self, id: ArenaId) -> Option<&T> {
+        self.values.get(id)
+    }
+
+    /// The value of the id mutably, or `None` if the arena has been cleared since allocating it
+    /// or it was allocated by another arena.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn get_mut(&mut self, id: ArenaId) -> Option<&mut T> {
+        self.values.get_mut(id)
+    }
+
+    /// Iterates over the ids and values in order of allocation.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn iter(&self) -> impl Iterator<Item = (ArenaId, &T)> + '_ {
+        self.values.iter()
+    }
+
+    /// Drops all values in the arena.
+    ///
+    /// All previously allocated ids stop resolving.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn clear(&mut self) {
+        self.values.clear()
+    }
+
+    /// The number of values in the arena.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn len(&self) -> usize {
+        self.values.values.len()
+    }
+
+    /// Whether the arena contains no values.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Default for BoundArena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, P> fmt::Debug for BoundArena<T, P>
+where
+    P: Policy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundArena").field("thread_id", &self.thread_id()).finish_non_exhaustive()
+    }
+}
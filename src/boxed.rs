@@ -0,0 +1,48 @@
+//! Boxed `!Send` futures and streams that can be polled from any thread.
+
+use std::{future::Future, pin::Pin};
+
+use futures_core::Stream;
+
+use crate::{PorterHandle, ThreadBound};
+
+/// A boxed `!Send` future that can be polled from any thread.
+///
+/// Created by [`boxed_send_local`].
+pub type SendLocalBoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send + 'static>>;
+
+/// A boxed `!Send` stream that can be polled from any thread.
+///
+/// Created by [`boxed_send_local_stream`].
+pub type SendLocalBoxStream<T> = Pin<Box<dyn Stream<Item = T> + Send + 'static>>;
+
+/// Binds the future to the current thread and boxes it, so that it can be polled from any thread.
+///
+/// The future is driven by the porter, as described by [`DispatchedFuture`](crate::DispatchedFuture).
+///
+/// ### Panics
+/// Panics if the porter does not belong to the current thread.
+#[track_caller]
+pub fn boxed_send_local<F>(porter: &PorterHandle, future: F) -> SendLocalBoxFuture<F::Output>
+where
+    F: Future + 'static,
+    F::Output: Send + 'static,
+{
+    Box::pin(ThreadBound::into_dispatched_future(ThreadBound::new(future), porter))
+}
+
+/// Binds the stream to the current thread and boxes it, so that it can be polled from any thread.
+///
+/// Polls from other threads are deferred to the porter, as described by
+/// [`LenientStream`](crate::LenientStream).
+///
+/// ### Panics
+/// Panics if the porter does not belong to the current thread.
+#[track_caller]
+pub fn boxed_send_local_stream<S>(porter: &PorterHandle, stream: S) -> SendLocalBoxStream<S::Item>
+where
+    S: Stream + 'static,
+    S::Item: Send + 'static,
+{
+    Box::pin(ThreadBound::into_lenient_stream(ThreadBound::new(stream), porter))
+}
@@ -0,0 +1,217 @@
+//! `!Send` callbacks callable from any thread.
+
+use std::{cell::RefCell, fmt, mem::ManuallyDrop, sync::Arc};
+
+use crate::{Call, PorterHandle, ThreadBound};
+
+type Callback<A, R> = Box<dyn Fn(A) -> R>;
+type CallbackMut<A, R> = RefCell<Box<dyn FnMut(A) -> R>>;
+
+struct Inner<C>
+where
+    C: 'static,
+{
+    callback: ManuallyDrop<ThreadBound<C>>,
+    porter: PorterHandle,
+}
+
+impl<C> Inner<C>
+where
+    C: 'static,
+{
+    #[track_caller]
+    fn new(porter: &PorterHandle, callback: C) -> Arc<Self> {
+        assert!(porter.is_owner(), "porter must belong to the current thread");
+        Arc::new(Self { callback: ManuallyDrop::new(ThreadBound::new(callback)), porter: porter.clone() })
+    }
+}
+
+impl<C> Drop for Inner<C>
+where
+    C: 'static,
+{
+    fn drop(&mut self) {
+        let callback = unsafe { ManuallyDrop::take(&mut self.callback) };
+        self.porter.drop_bound(callback);
+    }
+}
+
+/// A `!Send` callback that can be invoked from any thread.
+///
+/// Invocations are executed on the owner thread by its porter, for example
+/// to forward events from worker threads to a handler that holds `!Send` state.
+/// Multiple arguments are passed as a tuple.
+///
+/// The callback is [`Send`] + [`Sync`] and can be cloned freely.
+/// The closure is dropped on the owner thread once the last clone is gone.
+pub struct BoundCallback<A, R = ()>
+where
+    A: 'static,
+    R: 'static,
+{
+    inner: Arc<Inner<Callback<A, R>>>,
+}
+
+impl<A, R> BoundCallback<A, R>
+where
+    A: Send + 'static,
+    R: 'static,
+{
+    /// Binds the callback to the current thread, where it is executed by the porter.
+    ///
+    /// ### Panics
+    /// Panics if the porter does not belong to the current thread.
+    #[track_caller]
+    pub fn new(porter: &PorterHandle, f: impl Fn(A) -> R + 'static) -> Self {
+        let callback: Callback<A, R> = Box::new(f);
+        Self { inner: Inner::new(porter, callback) }
+    }
+
+    /// The porter executing the invocations.
+    pub fn porter(&self) -> &PorterHandle {
+        &self.inner.porter
+    }
+
+    /// Invokes the callback on the owner thread without waiting for it.
+    ///
+    /// The invocation is always queued, even when called from the owner thread.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    #[track_caller]
+    pub fn post(&self, args: A) {
+        let inner = self.inner.clone();
+        self.inner.porter.post(move || {
+            (inner.callback)(args);
+        });
+    }
+
+    /// Invokes the callback on the owner thread and returns its result.
+    ///
+    /// When called from the owner thread, the callback is invoked inline.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    /// Awaiting the returned future panics if the porter is dropped before
+    /// the callback has been invoked.
+    #[track_caller]
+    pub fn call(&self, args: A) -> Call<R>
+    where
+        R: Send,
+    {
+        let inner = self.inner.clone();
+        self.inner.porter.call(move || (inner.callback)(args))
+    }
+}
+
+impl<A, R> Clone for BoundCallback<A, R>
+where
+    A: 'static,
+    R: 'static,
+{
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<A, R> fmt::Debug for BoundCallback<A, R>
+where
+    A: 'static,
+    R: 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundCallback").field("thread_id", &self.inner.porter.thread_id()).finish()
+    }
+}
+
+/// A `!Send` callback with mutable state that can be invoked from any thread.
+///
+/// Like [`BoundCallback`], but the closure is [`FnMut`].
+/// Invocations are always queued, even when made from the owner thread, and
+/// executed one at a time by the porter in the order they were made.
+/// Multiple arguments are passed as a tuple.
+///
+/// The callback is [`Send`] + [`Sync`] and can be cloned freely.
+/// The closure is dropped on the owner thread once the last clone is gone.
+///
+/// ### Panics
+/// An invocation panics if the closure is invoked re-entrantly, i.e. if the
+/// porter is pumped from within the closure.
+pub struct BoundCallbackMut<A, R = ()>
+where
+    A: 'static,
+    R: 'static,
+{
+    inner: Arc<Inner<CallbackMut<A, R>>>,
+}
+
+impl<A, R> BoundCallbackMut<A, R>
+where
+    A: Send + 'static,
+    R: 'static,
+{
+    /// Binds the callback to the current thread, where it is executed by the porter.
+    ///
+    /// ### Panics
+    /// Panics if the porter does not belong to the current thread.
+    #[track_caller]
+    pub fn new(porter: &PorterHandle, f: impl FnMut(A) -> R + 'static) -> Self {
+        let callback: CallbackMut<A, R> = RefCell::new(Box::new(f));
+        Self { inner: Inner::new(porter, callback) }
+    }
+
+    /// The porter executing the invocations.
+    pub fn porter(&self) -> &PorterHandle {
+        &self.inner.porter
+    }
+
+    /// Invokes the callback on the owner thread without waiting for it.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    #[track_caller]
+    pub fn post(&self, args: A) {
+        let inner = self.inner.clone();
+        self.inner.porter.post(move || {
+            (inner.callback.borrow_mut())(args);
+        });
+    }
+
+    /// Invokes the callback on the owner thread and returns its result.
+    ///
+    /// The invocation is always queued, even when called from the owner thread,
+    /// so that the callback is never invoked re-entrantly from within itself.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    /// Awaiting the returned future panics if the porter is dropped before
+    /// the callback has been invoked.
+    #[track_caller]
+    pub fn call(&self, args: A) -> Call<R>
+    where
+        R: Send,
+    {
+        let inner = self.inner.clone();
+        self.inner.porter.call_queued(move || (inner.callback.borrow_mut())(args))
+    }
+}
+
+impl<A, R> Clone for BoundCallbackMut<A, R>
+where
+    A: 'static,
+    R: 'static,
+{
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<A, R> fmt::Debug for BoundCallbackMut<A, R>
+where
+    A: 'static,
+    R: 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundCallbackMut").field("thread_id", &self.inner.porter.thread_id()).finish()
+    }
+}
@@ -0,0 +1,77 @@
+//! Cancellation of remote calls.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    task::Waker,
+};
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    waiters: Mutex<(HashMap<u64, Waker>, u64)>,
+}
+
+impl Inner {
+    fn waiters(&self) -> MutexGuard<'_, (HashMap<u64, Waker>, u64)> {
+        self.waiters.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Cancels remote calls it has been passed to.
+///
+/// Cancelling skips calls that have not started executing on the owner thread yet
+/// and resolves their futures with [`CallError::Cancelled`](crate::CallError::Cancelled).
+/// A call that is already executing runs to completion, but its result is discarded.
+///
+/// The token can be cloned and shared between threads.
+#[derive(Clone, Default)]
+pub struct CancelToken {
+    inner: Arc<Inner>,
+}
+
+impl CancelToken {
+    /// Creates a new token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels all calls associated with this token.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Release);
+        let waiters = std::mem::take(&mut self.inner.waiters().0);
+        waiters.into_values().for_each(Waker::wake);
+    }
+
+    /// Whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Registers a waker to be woken on cancellation, replacing the
+    /// previous registration with the same id.
+    pub(crate) fn register(&self, id: &mut Option<u64>, waker: &Waker) {
+        let mut waiters = self.inner.waiters();
+        let (map, next) = &mut *waiters;
+        let id = *id.get_or_insert_with(|| {
+            *next += 1;
+            *next
+        });
+        map.insert(id, waker.clone());
+    }
+
+    /// Removes a waker registration.
+    pub(crate) fn unregister(&self, id: u64) {
+        self.inner.waiters().0.remove(&id);
+    }
+}
+
+impl fmt::Debug for CancelToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CancelToken").field("cancelled", &self.is_cancelled()).finish()
+    }
+}
@@ -0,0 +1,109 @@
+//! Helpers for thread-bound shared cells.
+
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt,
+    rc::Rc,
+};
+
+use crate::{ThreadBound, WrongThread};
+
+/// Error returned when borrowing the contents of a thread-bound [`RefCell`] fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundBorrowError {
+    /// The value was accessed from another thread than its owner thread.
+    WrongThread(WrongThread),
+    /// The cell is already mutably borrowed, or borrowed at all for a mutable borrow.
+    AlreadyBorrowed,
+}
+
+impl fmt::Display for BoundBorrowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::WrongThread(err) => err.fmt(f),
+            Self::AlreadyBorrowed => write!(f, "value is already borrowed"),
+        }
+    }
+}
+
+impl std::error::Error for BoundBorrowError {}
+
+impl From<WrongThread> for BoundBorrowError {
+    fn from(err: WrongThread) -> Self {
+        Self::WrongThread(err)
+    }
+}
+
+impl<T> ThreadBound<Rc<RefCell<T>>> {
+    /// Immutably borrows the contents of the cell.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread or if the
+    /// contents are mutably borrowed.
+    #[track_caller]
+    pub fn borrow_inner(this: &Self) -> Ref<'_, T> {
+        (**this).borrow()
+    }
+
+    /// Mutably borrows the contents of the cell.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread or if the
+    /// contents are borrowed.
+    #[track_caller]
+    pub fn borrow_inner_mut(this: &Self) -> RefMut<'_, T> {
+        (**this).borrow_mut()
+    }
+
+    /// Immutably borrows the contents of the cell, failing if called from
+    /// another thread or if the contents are mutably borrowed.
+    pub fn try_borrow_inner(this: &Self) -> Result<Ref<'_, T>, BoundBorrowError> {
+        Self::try_get(this)?.try_borrow().map_err(|_| BoundBorrowError::AlreadyBorrowed)
+    }
+
+    /// Mutably borrows the contents of the cell, failing if called from
+    /// another thread or if the contents are borrowed.
+    pub fn try_borrow_inner_mut(this: &Self) -> Result<RefMut<'_, T>, BoundBorrowError> {
+        Self::try_get(this)?.try_borrow_mut().map_err(|_| BoundBorrowError::AlreadyBorrowed)
+    }
+}
+
+impl<T> ThreadBound<Rc<Cell<T>>> {
+    /// A copy of the contents of the cell.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn get_inner(this: &Self) -> T
+    where
+        T: Copy,
+    {
+        (**this).get()
+    }
+
+    /// Sets the contents of the cell.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn set_inner(this: &Self, value: T) {
+        (**this).set(value)
+    }
+
+    /// Replaces the contents of the cell, returning the old contents.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn replace_inner(this: &Self, value: T) -> T {
+        (**this).replace(value)
+    }
+
+    /// A copy of the contents of the cell, failing if called from another thread.
+    pub fn try_get_inner(this: &Self) -> Result<T, BoundBorrowError>
+    where
+        T: Copy,
+    {
+        Ok(Self::try_get(this)?.get())
+    }
+}
@@ -0,0 +1,154 @@
+//! Channels with [`Send`] endpoints carrying thread-bound items.
+
+use std::{
+    fmt,
+    sync::{
+        mpsc::{self, RecvError, RecvTimeoutError, SendError, TryRecvError},
+        Mutex, MutexGuard, PoisonError,
+    },
+    time::Duration,
+};
+
+use crate::{Panicking, Policy, Strict, ThreadBound};
+
+/// Creates a channel whose items are bound to the thread that sent them.
+///
+/// See [`BoundSender`] and [`BoundReceiver`] for details.
+pub fn bound_channel<T>() -> (BoundSender<T>, BoundReceiver<T>) {
+    bound_channel_with_policy()
+}
+
+/// Creates a channel whose items are bound to the thread that sent them using the policy `P`.
+///
+/// The policy also determines what happens to items that are still in the channel
+/// when it is dropped on another thread than the one that sent them:
+/// under [`Strict`] this panics, while under [`Leaky`](crate::Leaky) the items are leaked.
+pub fn bound_channel_with_policy<T, P>() -> (BoundSender<T, P>, BoundReceiver<T, P>)
+where
+    P: Policy,
+{
+    let (tx, rx) = mpsc::channel();
+    (BoundSender { tx }, BoundReceiver { rx: Mutex::new(rx) })
+}
+
+/// The sending half of a [`bound_channel`].
+///
+/// The sender is [`Send`] + [`Sync`] and can be cloned freely, so that it can be kept
+/// in shared state, while the items it sends are bound to the sending thread.
+pub struct BoundSender<T, P = Strict>
+where
+    P: Policy,
+{
+    tx: mpsc::Sender<ThreadBound<T, P>>,
+}
+
+impl<T, P> BoundSender<T, P>
+where
+    P: Policy,
+{
+    /// Binds the value to the current thread and sends it.
+    ///
+    /// Returns the value if the receiver has been dropped.
+    #[track_caller]
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.send_bound(ThreadBound::with_policy(value)).map_err(|SendError(bound)| {
+            let Ok(value) = ThreadBound::try_into_inner(bound) else { unreachable!() };
+            SendError(value)
+        })
+    }
+
+    /// Sends a value that is already bound to a thread.
+    ///
+    /// Returns the value if the receiver has been dropped.
+    pub fn send_bound(&self, value: ThreadBound<T, P>) -> Result<(), SendError<ThreadBound<T, P>>> {
+        self.tx.send(value)
+    }
+}
+
+impl<T, P> Clone for BoundSender<T, P>
+where
+    P: Policy,
+{
+    fn clone(&self) -> Self {
+        Self { tx: self.tx.clone() }
+    }
+}
+
+impl<T, P> fmt::Debug for BoundSender<T, P>
+where
+    P: Policy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundSender").finish_non_exhaustive()
+    }
+}
+
+/// The receiving half of a [`bound_channel`].
+///
+/// The receiver is [`Send`] + [`Sync`], so that it can be kept in shared state.
+/// Received items are bound to the thread that sent them and can only be used there.
+/// If multiple threads receive concurrently, they take turns.
+pub struct BoundReceiver<T, P = Strict>
+where
+    P: Policy,
+{
+    rx: Mutex<mpsc::Receiver<ThreadBound<T, P>>>,
+}
+
+impl<T, P> BoundReceiver<T, P>
+where
+    P: Policy,
+{
+    fn rx(&self) -> MutexGuard<'_, mpsc::Receiver<ThreadBound<T, P>>> {
+        self.rx.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Waits for the next item.
+    ///
+    /// Fails if all senders have been dropped and the channel is empty.
+    pub fn recv(&self) -> Result<ThreadBound<T, P>, RecvError> {
+        self.rx().recv()
+    }
+
+    /// Waits for the next item until the timeout elapses.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<ThreadBound<T, P>, RecvTimeoutError> {
+        self.rx().recv_timeout(timeout)
+    }
+
+    /// Receives the next item without waiting.
+    pub fn try_recv(&self) -> Result<ThreadBound<T, P>, TryRecvError> {
+        self.rx().try_recv()
+    }
+}
+
+impl<T, P> BoundReceiver<T, P>
+where
+    P: Panicking,
+{
+    /// Waits for the next item and takes its value out.
+    ///
+    /// ### Panics
+    /// Panics if the item was sent from another thread than the current thread.
+    #[track_caller]
+    pub fn recv_inner(&self) -> Result<T, RecvError> {
+        self.recv().map(ThreadBound::into_inner)
+    }
+
+    /// Receives the next item without waiting and takes its value out.
+    ///
+    /// ### Panics
+    /// Panics if the item was sent from another thread than the current thread.
+    #[track_caller]
+    pub fn try_recv_inner(&self) -> Result<T, TryRecvError> {
+        self.try_recv().map(ThreadBound::into_inner)
+    }
+}
+
+impl<T, P> fmt::Debug for BoundReceiver<T, P>
+where
+    P: Policy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundReceiver").finish_non_exhaustive()
+    }
+}
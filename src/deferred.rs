@@ -0,0 +1,111 @@
+//! Deferred destruction of values on their owner thread.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+use std::{any::Any, cell::RefCell, collections::VecDeque};
+
+use crate::ThreadBound;
+
+thread_local! {
+    static DEFERRED: RefCell<VecDeque<Box<dyn Any>>> = const { RefCell::new(VecDeque::new()) };
+}
+
+/// Queues the value for destruction on the current thread.
+fn defer(value: Box<dyn Any>) {
+    DEFERRED.with(|deferred| deferred.borrow_mut().push_back(value));
+}
+
+/// Drops the next value queued for destruction on the current thread.
+///
+/// Returns whether a value was dropped.
+fn drop_next() -> bool {
+    // The queue must not be borrowed while dropping, since the value may defer further drops.
+    let next = DEFERRED.with(|deferred| deferred.borrow_mut().pop_front());
+    next.is_some()
+}
+
+/// Drops all values queued for destruction on the current thread
+/// by [`ThreadBound::defer_drop`].
+///
+/// This includes values deferred while flushing.
+/// A [`Porter`](crate::Porter) does this automatically whenever it becomes idle.
+///
+/// Returns the number of values dropped.
+pub fn flush_deferred_all() -> usize {
+    let mut dropped = 0;
+    while drop_next() {
+        dropped += 1;
+    }
+    dropped
+}
+
+/// Drops values queued for destruction on the current thread until `budget` is exhausted.
+///
+/// At least one value is dropped if any is queued; since a drop cannot be interrupted,
+/// the last one may exceed the budget.
+/// This allows amortizing expensive teardown across frames.
+///
+/// This is not available on WebAssembly targets, where the system clock cannot be read
+/// through [`Instant`]; use [`flush_deferred_until`] with a clock of the host instead,
+/// for example `performance.now()`.
+///
+/// Returns the number of values dropped.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn flush_deferred(budget: Duration) -> usize {
+    let start = Instant::now();
+    flush_deferred_until(|| start.elapsed() >= budget)
+}
+
+/// Drops values queued for destruction on the current thread until `expired` returns `true`.
+///
+/// `expired` is checked before each drop after the first; at least one value is dropped
+/// if any is queued.
+/// This is the clock-agnostic variant of `flush_deferred`, which also works on
+/// WebAssembly targets.
+///
+/// Returns the number of values dropped.
+pub fn flush_deferred_until(mut expired: impl FnMut() -> bool) -> usize {
+    let mut dropped = 0;
+    while (dropped == 0 || !expired()) && drop_next() {
+        dropped += 1;
+    }
+    dropped
+}
+
+/// Drops at most `max` values queued for destruction on the current thread.
+///
+/// Returns the number of values dropped.
+pub fn flush_deferred_count(max: usize) -> usize {
+    let mut dropped = 0;
+    while dropped < max && drop_next() {
+        dropped += 1;
+    }
+    dropped
+}
+
+/// The number of values queued for destruction on the current thread.
+pub fn deferred_len() -> usize {
+    DEFERRED.with(|deferred| deferred.borrow().len())
+}
+
+impl<T> ThreadBound<T>
+where
+    T: 'static,
+{
+    /// Queues the value for destruction at a later time on the owner thread.
+    ///
+    /// Queued values are dropped when a [`Porter`](crate::Porter) of the owner thread
+    /// becomes idle or when [`flush_deferred_all`] is called, and within a budget
+    /// by `flush_deferred`, [`flush_deferred_until`] and [`flush_deferred_count`].
+    /// This avoids hitches caused by the synchronous destruction of expensive
+    /// resources, for example in the middle of rendering a frame.
+    /// A registered [drop hook](Self::on_drop) is called when the value is dropped.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn defer_drop(this: Self) {
+        this.check();
+        defer(Box::new(this));
+    }
+}
@@ -0,0 +1,117 @@
+//! Poll thread-bound futures from any thread.
+
+use std::{
+    fmt,
+    future::Future,
+    panic::resume_unwind,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{JoinError, JoinHandle, PorterHandle, ThreadBound};
+
+enum State<F>
+where
+    F: Future,
+{
+    Idle(ThreadBound<F>, PorterHandle),
+    Running(JoinHandle<F::Output>),
+    Done,
+}
+
+/// A thread-bound future that can be polled from any thread.
+///
+/// Created by [`ThreadBound::into_dispatched_future`].
+///
+/// When first polled, the future is handed to the [`Porter`](crate::Porter) of its
+/// owner thread, which drives it from then on.
+/// Wake-ups are proxied back to the thread polling this adapter.
+///
+/// Dropping the adapter drops the inner future on its owner thread.
+///
+/// ### Panics
+/// Polling panics if the porter is dropped before the future completes.
+/// A panic raised by the inner future is propagated to the polling thread.
+#[must_use = "futures do nothing unless polled"]
+pub struct DispatchedFuture<F>
+where
+    F: Future + 'static,
+{
+    state: State<F>,
+}
+
+impl<F> DispatchedFuture<F>
+where
+    F: Future + 'static,
+    F::Output: Send + 'static,
+{
+    pub(crate) fn new(future: ThreadBound<F>, porter: PorterHandle) -> Self {
+        Self { state: State::Idle(future, porter) }
+    }
+}
+
+// The inner future is never pinned by the adapter.
+impl<F> Unpin for DispatchedFuture<F> where F: Future + 'static {}
+
+impl<F> fmt::Debug for DispatchedFuture<F>
+where
+    F: Future + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let state = match &self.state {
+            State::Idle(..) => "Idle",
+            State::Running(_) => "Running",
+            State::Done => "Done",
+        };
+        f.debug_struct("DispatchedFuture").field("state", &state).finish()
+    }
+}
+
+impl<F> Future for DispatchedFuture<F>
+where
+    F: Future + 'static,
+    F::Output: Send + 'static,
+{
+    type Output = F::Output;
+
+    #[track_caller]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<F::Output> {
+        if let State::Idle(..) = &self.state {
+            let State::Idle(future, porter) = std::mem::replace(&mut self.state, State::Done) else {
+                unreachable!()
+            };
+            match porter.spawn_bound(future) {
+                Some(handle) => self.state = State::Running(handle),
+                None => panic!("porter of thread {:?} is closed", porter.thread_id()),
+            }
+        }
+
+        let State::Running(handle) = &mut self.state else {
+            panic!("DispatchedFuture polled after completion");
+        };
+        let result = match Pin::new(handle).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        self.state = State::Done;
+        match result {
+            Ok(output) => Poll::Ready(output),
+            Err(JoinError::Panicked(payload)) => resume_unwind(payload),
+            Err(JoinError::Cancelled) => panic!("porter was closed before the future completed"),
+        }
+    }
+}
+
+impl<F> Drop for DispatchedFuture<F>
+where
+    F: Future + 'static,
+{
+    fn drop(&mut self) {
+        match std::mem::replace(&mut self.state, State::Done) {
+            State::Idle(future, porter) => porter.drop_bound(future),
+            State::Running(handle) => handle.abort(),
+            State::Done => (),
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! Extension traits and macros for binding values fluently.
+
+use std::{
+    fmt,
+    iter::FusedIterator,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+
+use crate::{MetaBound, ThreadBound};
+
+/// Binds values to the current thread using method syntax.
+///
+/// This is implemented for all sized types, so that for example
+/// `document().bound()` reads naturally at the call site.
+pub trait ThreadBoundExt: Sized {
+    /// Binds the value to the current thread.
+    fn bound(self) -> ThreadBound<Self> {
+        ThreadBound::new(self)
+    }
+
+    /// Binds the value to the current thread and labels it for diagnostics.
+    fn bound_named(self, label: &'static str) -> ThreadBound<Self> {
+        ThreadBound::new_named(self, label)
+    }
+
+    /// Binds the value to the current thread and attaches metadata that is
+    /// accessible from any thread.
+    fn bound_with_meta<M>(self, meta: M) -> MetaBound<Self, M> {
+        MetaBound::new(self, meta)
+    }
+}
+
+impl<T> ThreadBoundExt for T {}
+
+/// Binds the items of iterators to the iterating thread.
+pub trait BoundIteratorExt: Iterator + Sized {
+    /// Wraps each item in a [`ThreadBound`] bound to the thread that yielded it.
+    ///
+    /// This allows collecting `!Send` items into a [`Send`] container, which is later
+    /// consumed on the same thread.
+    fn bound_items(self) -> BoundItems<Self> {
+        BoundItems { iter: self }
+    }
+}
+
+impl<I> BoundIteratorExt for I where I: Iterator {}
+
+/// Iterator binding its items to the iterating thread, created by [`BoundIteratorExt::bound_items`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct BoundItems<I> {
+    iter: I,
+}
+
+impl<I> BoundItems<I> {
+    /// Returns the inner iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I> Iterator for BoundItems<I>
+where
+    I: Iterator,
+{
+    type Item = ThreadBound<I::Item>;
+
+    #[track_caller]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(ThreadBound::new)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterator for BoundItems<I>
+where
+    I: DoubleEndedIterator,
+{
+    #[track_caller]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(ThreadBound::new)
+    }
+}
+
+impl<I> ExactSizeIterator for BoundItems<I> where I: ExactSizeIterator {}
+
+impl<I> FusedIterator for BoundItems<I> where I: FusedIterator {}
+
+impl<I> fmt::Debug for BoundItems<I>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundItems").field("iter", &self.iter).finish()
+    }
+}
+
+/// Binds the items of streams to the polling thread.
+pub trait BoundStreamExt: Stream + Sized {
+    /// Wraps each item in a [`ThreadBound`] bound to the thread that polled it.
+    ///
+    /// This way a [`Send`] stream of `!Send` items, such as a channel of JavaScript
+    /// values, satisfies bounds requiring [`Send`] items.
+    fn map_bound(self) -> MapBound<Self> {
+        MapBound { stream: self }
+    }
+}
+
+impl<S> BoundStreamExt for S where S: Stream {}
+
+/// Stream binding its items to the polling thread, created by [`BoundStreamExt::map_bound`].
+#[must_use = "streams do nothing unless polled"]
+pub struct MapBound<S> {
+    stream: S,
+}
+
+impl<S> MapBound<S> {
+    /// Returns the inner stream.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S> Stream for MapBound<S>
+where
+    S: Stream,
+{
+    type Item = ThreadBound<S::Item>;
+
+    #[track_caller]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        // The inner stream is structurally pinned and never moved.
+        let stream = unsafe { self.map_unchecked_mut(|s| &mut s.stream) };
+        stream.poll_next(cx).map(|item| item.map(ThreadBound::new))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S> fmt::Debug for MapBound<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MapBound").field("stream", &self.stream).finish()
+    }
+}
+
+/// Binds several values to the current thread at once, labeling each for diagnostics.
+///
+/// Each entry `name = value` declares a variable `name` holding the bound value,
+/// labeled with the name of the variable.
+/// A different label can be given as `label: name = value`.
+/// The policy shared by all values defaults to [`Strict`](crate::Strict) and
+/// can be given in angle brackets before the entries.
+///
+/// ```
+/// use std::rc::Rc;
+/// use threadporter::{bound, ThreadBound};
+///
+/// bound! {
+///     canvas: ctx = Rc::new("2d"),
+///     audio = Rc::new(44_100),
+/// }
+/// assert_eq!(ThreadBound::label(&ctx), Some("canvas"));
+/// assert_eq!(ThreadBound::label(&audio), Some("audio"));
+///
+/// bound! { <threadporter::Leaky> document = Rc::new("body") }
+/// assert_eq!(**document, "body");
+/// ```
+#[macro_export]
+macro_rules! bound {
+    (@bind [$policy:ty] $label:ident : $name:ident = $value:expr $(, $($rest:tt)*)?) => {
+        let $name = $crate::ThreadBound::<_, $policy>::with_policy_named($value, stringify!($label));
+        $($crate::bound!(@bind [$policy] $($rest)*);)?
+    };
+    (@bind [$policy:ty] $name:ident = $value:expr $(, $($rest:tt)*)?) => {
+        let $name = $crate::ThreadBound::<_, $policy>::with_policy_named($value, stringify!($name));
+        $($crate::bound!(@bind [$policy] $($rest)*);)?
+    };
+    (@bind [$policy:ty]) => {};
+    (<$policy:ty> $($entries:tt)*) => {
+        $crate::bound!(@bind [$policy] $($entries)*);
+    };
+    ($($entries:tt)*) => {
+        $crate::bound!(@bind [$crate::Strict] $($entries)*);
+    };
+}
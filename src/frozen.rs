@@ -0,0 +1,108 @@
+//! Read-only views of thread-bound values at the end of their life.
+
+use std::{any::Any, fmt, ops::Deref, thread::ThreadId};
+
+use crate::{PorterHandle, ThreadBound};
+
+struct Parked {
+    value: Option<ThreadBound<Box<dyn Any>>>,
+    porter: PorterHandle,
+}
+
+impl Drop for Parked {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            self.porter.drop_bound(value);
+        }
+    }
+}
+
+/// Metadata extracted from a thread-bound value when it was frozen, accessible from any thread.
+///
+/// Created by [`ThreadBound::freeze`] or [`ThreadBound::freeze_parked`].
+///
+/// This is useful at the end of the life of a resource, when other threads still
+/// hold references to it for bookkeeping.
+/// The metadata is accessible through [`Deref`].
+pub struct FrozenBound<M> {
+    meta: M,
+    thread_id: ThreadId,
+    parked: Option<Parked>,
+}
+
+impl<M> FrozenBound<M> {
+    /// The id of the thread that owned the value.
+    pub fn thread_id(this: &Self) -> ThreadId {
+        this.thread_id
+    }
+
+    /// Whether the value is parked until this is dropped.
+    pub fn is_parked(this: &Self) -> bool {
+        this.parked.is_some()
+    }
+
+    /// Takes the metadata out, releasing a parked value.
+    pub fn into_meta(this: Self) -> M {
+        this.meta
+    }
+}
+
+impl<M> Deref for FrozenBound<M> {
+    type Target = M;
+
+    fn deref(&self) -> &M {
+        &self.meta
+    }
+}
+
+impl<M> fmt::Debug for FrozenBound<M>
+where
+    M: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FrozenBound")
+            .field("thread_id", &self.thread_id)
+            .field("meta", &self.meta)
+            .field("parked", &self.parked.is_some())
+            .finish()
+    }
+}
+
+impl<T> ThreadBound<T> {
+    /// Extracts metadata from the value using `f` and drops the value.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn freeze<M>(this: Self, f: impl FnOnce(&T) -> M) -> FrozenBound<M> {
+        let thread_id = Self::thread_id(&this);
+        let meta = f(&this);
+        drop(this);
+        FrozenBound { meta, thread_id, parked: None }
+    }
+
+    /// Extracts metadata from the value using `f` and parks the value until
+    /// the returned view is dropped.
+    ///
+    /// The parked value is not accessible anymore and is dropped on the owner
+    /// thread by the porter, keeping its label and [drop hook](ThreadBound::on_drop).
+    /// If the porter has been dropped by then, the value is leaked.
+    ///
+    /// ### Panics
+    /// Panics if the porter does not belong to the owner thread or if called
+    /// from another thread than the owner thread.
+    #[track_caller]
+    pub fn freeze_parked<M>(this: Self, porter: &PorterHandle, f: impl FnOnce(&T) -> M) -> FrozenBound<M>
+    where
+        T: 'static,
+    {
+        let thread_id = Self::thread_id(&this);
+        assert_eq!(porter.thread_id(), thread_id, "porter must belong to the owner thread");
+        let meta = f(&this);
+        FrozenBound {
+            meta,
+            thread_id,
+            parked: Some(Parked { value: Some(Self::into_any(this)), porter: porter.clone() }),
+        }
+    }
+}
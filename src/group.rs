@@ -0,0 +1,221 @@
+//! Bind multiple values as one unit.
+
+use std::{
+    fmt,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+    thread::ThreadId,
+};
+
+use crate::{Panicking, Policy, PorterHandle, Strict, ThreadBound};
+
+/// A tuple of values that can be split into individually bound values.
+pub trait BoundTuple: Sized {
+    /// The tuple of individually bound values.
+    type Bound;
+
+    /// Splits the bound tuple into individually bound values sharing its owner thread and label.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    fn split(bound: ThreadBound<Self>) -> Self::Bound;
+}
+
+macro_rules! impl_bound_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> BoundTuple for ($($name,)+) {
+            type Bound = ($(ThreadBound<$name>,)+);
+
+            #[track_caller]
+            #[allow(non_snake_case)]
+            fn split(bound: ThreadBound<Self>) -> Self::Bound {
+                let label = ThreadBound::label(&bound);
+                let ($($name,)+) = ThreadBound::into_inner(bound);
+                ($(ThreadBound::with_label($name, label),)+)
+            }
+        }
+    };
+}
+
+impl_bound_tuple!(A);
+impl_bound_tuple!(A, B);
+impl_bound_tuple!(A, B, C);
+impl_bound_tuple!(A, B, C, D);
+impl_bound_tuple!(A, B, C, D, E);
+impl_bound_tuple!(A, B, C, D, E, F);
+impl_bound_tuple!(A, B, C, D, E, F, G);
+impl_bound_tuple!(A, B, C, D, E, F, G, H);
+
+/// Several related values bound to the current thread as one unit.
+///
+/// The values, usually a tuple, share a single owner thread and label and are
+/// moved, unwrapped and dropped together.
+/// The group is [`Send`] + [`Sync`] and can be passed between threads as a whole.
+///
+/// If [drop forwarding](Self::forward_drops) is enabled, dropping the group on
+/// another thread drops all its values on the owner thread through its
+/// [`Porter`](crate::Porter).
+///
+/// The values are accessible through [`Deref`] to [`ThreadBound`].
+///
+/// The values are bound using the [`Policy`] `P`, which defaults to [`Strict`].
+///
+/// ### Panics
+/// Under a [panicking](Panicking) policy, accessing the values from another thread panics.
+/// Dropping the group on another thread without drop forwarding panics if its
+/// values need drop, unless the policy leaks them.
+pub struct BoundGroup<T, P = Strict>
+where
+    T: 'static,
+    P: Policy,
+{
+    values: ManuallyDrop<ThreadBound<T, P>>,
+    porter: Option<PorterHandle>,
+}
+
+impl<T> BoundGroup<T>
+where
+    T: 'static,
+{
+    /// Binds the values to the current thread.
+    #[track_caller]
+    pub fn new(values: T) -> Self {
+        Self::from_bound(ThreadBound::new(values))
+    }
+
+    /// Binds the values to the current thread and labels them for diagnostics.
+    #[track_caller]
+    pub fn new_named(values: T, label: &'static str) -> Self {
+        Self::from_bound(ThreadBound::new_named(values, label))
+    }
+
+    /// Splits the group into individually bound values sharing its owner thread and label.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn split(this: Self) -> T::Bound
+    where
+        T: BoundTuple,
+    {
+        T::split(Self::into_bound(this))
+    }
+}
+
+impl<T, P> BoundGroup<T, P>
+where
+    T: 'static,
+    P: Policy,
+{
+    /// Binds the values to the current thread using the policy `P`.
+    #[track_caller]
+    pub fn with_policy(values: T) -> Self {
+        Self::from_bound(ThreadBound::with_policy(values))
+    }
+
+    /// Binds the values to the current thread using the policy `P` and labels them for diagnostics.
+    #[track_caller]
+    pub fn with_policy_named(values: T, label: &'static str) -> Self {
+        Self::from_bound(ThreadBound::with_policy_named(values, label))
+    }
+
+    /// Groups values that are already bound together.
+    pub fn from_bound(values: ThreadBound<T, P>) -> Self {
+        Self { values: ManuallyDrop::new(values), porter: None }
+    }
+
+    /// Forwards drops from other threads to the owner thread through the porter.
+    ///
+    /// The values are leaked if the porter has been dropped.
+    ///
+    /// ### Panics
+    /// Panics if the porter does not belong to the owner thread.
+    #[track_caller]
+    pub fn forward_drops(mut this: Self, porter: &PorterHandle) -> Self {
+        assert_eq!(porter.thread_id(), this.thread_id(), "porter must belong to the owner thread");
+        this.porter = Some(porter.clone());
+        this
+    }
+
+    /// The id of the thread that is allowed to access the values.
+    pub fn thread_id(&self) -> ThreadId {
+        ThreadBound::thread_id(&self.values)
+    }
+
+    /// The label used for diagnostics.
+    pub fn label(&self) -> Option<&'static str> {
+        ThreadBound::label(&self.values)
+    }
+
+    /// Takes the values out of the group, keeping them bound together.
+    pub fn into_bound(mut this: Self) -> ThreadBound<T, P> {
+        let values = unsafe { ManuallyDrop::take(&mut this.values) };
+        this.porter = None;
+        std::mem::forget(this);
+        values
+    }
+}
+
+impl<T, P> BoundGroup<T, P>
+where
+    T: 'static,
+    P: Panicking,
+{
+    /// Takes the values out.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn into_inner(this: Self) -> T {
+        ThreadBound::into_inner(Self::into_bound(this))
+    }
+}
+
+impl<T, P> Deref for BoundGroup<T, P>
+where
+    T: 'static,
+    P: Policy,
+{
+    type Target = ThreadBound<T, P>;
+
+    fn deref(&self) -> &ThreadBound<T, P> {
+        &self.values
+    }
+}
+
+impl<T, P> DerefMut for BoundGroup<T, P>
+where
+    T: 'static,
+    P: Policy,
+{
+    fn deref_mut(&mut self) -> &mut ThreadBound<T, P> {
+        &mut self.values
+    }
+}
+
+impl<T, P> fmt::Debug for BoundGroup<T, P>
+where
+    T: fmt::Debug + 'static,
+    P: Panicking,
+{
+    #[track_caller]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundGroup").field("values", &*self.values).finish()
+    }
+}
+
+impl<T, P> Drop for BoundGroup<T, P>
+where
+    T: 'static,
+    P: Policy,
+{
+    #[track_caller]
+    fn drop(&mut self) {
+        let values = unsafe { ManuallyDrop::take(&mut self.values) };
+        match &self.porter {
+            Some(porter) => porter.drop_bound(values),
+            None => drop(values),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Access guards for thread-bound values.
+
+use std::{
+    any::type_name,
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    thread,
+};
+
+use crate::{Policy, ThreadBound};
+
+/// Makes a guard `!Send` + `!Sync`.
+type NotSend = PhantomData<*const ()>;
+
+/// Shared access to a thread-bound value, checked once when acquired.
+///
+/// Created by [`ThreadBound::guard`](crate::ThreadBound::guard).
+///
+/// The guard is `!Send`, so that a future holding it across an `.await` is
+/// `!Send` as well and cannot be moved to another thread by a work-stealing executor.
+/// For short operations prefer [`ThreadBound::inspect`](crate::ThreadBound::inspect).
+#[must_use = "the guard grants access until it is dropped"]
+pub struct BoundGuard<'a, T> {
+    value: &'a T,
+    _not_send: NotSend,
+}
+
+impl<'a, T> BoundGuard<'a, T> {
+    pub(crate) fn new(value: &'a T) -> Self {
+        Self { value, _not_send: PhantomData }
+    }
+}
+
+impl<T> Deref for BoundGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T> fmt::Debug for BoundGuard<'_, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/// Exclusive access to a thread-bound value, checked once when acquired.
+///
+/// Created by [`ThreadBound::guard_mut`](crate::ThreadBound::guard_mut).
+///
+/// The guard is `!Send`, so that a future holding it across an `.await` is
+/// `!Send` as well and cannot be moved to another thread by a work-stealing executor.
+/// For short operations prefer [`ThreadBound::update`](crate::ThreadBound::update).
+#[must_use = "the guard grants access until it is dropped"]
+pub struct BoundGuardMut<'a, T> {
+    value: &'a mut T,
+    _not_send: NotSend,
+}
+
+impl<'a, T> BoundGuardMut<'a, T> {
+    pub(crate) fn new(value: &'a mut T) -> Self {
+        Self { value, _not_send: PhantomData }
+    }
+}
+
+impl<T> Deref for BoundGuardMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T> DerefMut for BoundGuardMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
+
+impl<T> fmt::Debug for BoundGuardMut<'_, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/// A thread-bound value whose value can be taken out for the duration of a scope.
+///
+/// Created by [`ThreadBound::scope_take`](crate::ThreadBound::scope_take).
+///
+/// While the value is taken out, the binding is empty and cannot be used.
+///
+/// ### Panics
+/// Dropping the guard panics if the value has been taken out and not restored,
+/// unless the thread is already panicking, in which case the binding is left empty
+/// and further use of it panics.
+#[must_use = "the value must be restored before the guard is dropped"]
+pub struct ScopeTake<'a, T, P>
+where
+    P: Policy,
+{
+    bound: &'a mut ThreadBound<T, P>,
+    _not_send: NotSend,
+}
+
+impl<'a, T, P> ScopeTake<'a, T, P>
+where
+    P: Policy,
+{
+    pub(crate) fn new(bound: &'a mut ThreadBound<T, P>) -> Self {
+        Self { bound, _not_send: PhantomData }
+    }
+
+    /// Moves the value out of the binding.
+    ///
+    /// ### Panics
+    /// Panics if the value has already been taken out and not restored.
+    #[track_caller]
+    pub fn take(&mut self) -> T {
+        assert!(!self.is_taken(), "value has already been taken out");
+        ThreadBound::take_value(self.bound)
+    }
+
+    /// Puts the value back into the binding.
+    ///
+    /// If the value has not been taken out, it is replaced and the previous value is dropped.
+    pub fn restore(&mut self, value: T) {
+        ThreadBound::restore_value(self.bound, value)
+    }
+
+    /// Whether the value is currently taken out.
+    pub fn is_taken(&self) -> bool {
+        ThreadBound::is_taken(self.bound)
+    }
+}
+
+impl<T, P> fmt::Debug for ScopeTake<'_, T, P>
+where
+    P: Policy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ScopeTake").field("taken", &self.is_taken()).finish()
+    }
+}
+
+impl<T, P> Drop for ScopeTake<'_, T, P>
+where
+    P: Policy,
+{
+    fn drop(&mut self) {
+        if self.is_taken() && !thread::panicking() {
+            panic!("value of {} was taken out and not restored", type_name::<T>());
+        }
+    }
+}
@@ -0,0 +1,508 @@
+//! Thread-bound futures, streams and sinks that defer foreign use to the owner thread.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    mem::ManuallyDrop,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use futures_core::Stream;
+use futures_sink::Sink;
+
+use crate::{PorterHandle, ThreadBound};
+
+/// A value kept on its owner thread together with state shared with the polling threads.
+struct Shared<T, S>
+where
+    T: 'static,
+{
+    value: ManuallyDrop<ThreadBound<RefCell<T>>>,
+    state: Mutex<S>,
+    waker: Mutex<Option<Waker>>,
+    scheduled: AtomicBool,
+    porter: PorterHandle,
+}
+
+impl<T, S> Shared<T, S>
+where
+    T: 'static,
+    S: Send + 'static,
+{
+    /// Moves the value into shared storage; must be called on the owner thread.
+    fn new(value: T, state: S, porter: PorterHandle) -> Arc<Self> {
+        Arc::new(Self {
+            value: ManuallyDrop::new(ThreadBound::new(RefCell::new(value))),
+            state: Mutex::new(state),
+            waker: Mutex::new(None),
+            scheduled: AtomicBool::new(false),
+            porter,
+        })
+    }
+
+    fn state(&self) -> MutexGuard<'_, S> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_owner(&self) -> bool {
+        ThreadBound::is_usable(&self.value)
+    }
+
+    /// Registers the waker of the polling task.
+    fn register(&self, waker: &Waker) {
+        *self.waker.lock().unwrap_or_else(PoisonError::into_inner) = Some(waker.clone());
+    }
+
+    /// Wakes the polling task.
+    fn wake(&self) {
+        let waker = self.waker.lock().unwrap_or_else(PoisonError::into_inner).take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Schedules `f` on the owner thread unless it is scheduled already.
+    ///
+    /// `f` is passed a context whose waker wakes the polling task.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been closed, since the value can make no further progress.
+    #[track_caller]
+    fn schedule(self: &Arc<Self>, f: fn(&Self, &mut T, &mut Context)) {
+        if self.scheduled.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let this = self.clone();
+        let posted = self.porter.post_bypass(move || {
+            this.scheduled.store(false, Ordering::Release);
+            let waker = Waker::from(Arc::new(WakePoller(this.clone())));
+            f(&this, &mut this.value.borrow_mut(), &mut Context::from_waker(&waker));
+        });
+        if !posted {
+            // Allow later uses to fail in the same way instead of waiting for the lost poll.
+            self.scheduled.store(false, Ordering::Release);
+            panic!("porter of thread {:?} is closed", self.porter.thread_id());
+        }
+    }
+}
+
+impl<T, S> Drop for Shared<T, S>
+where
+    T: 'static,
+{
+    fn drop(&mut self) {
+        let value = unsafe { ManuallyDrop::take(&mut self.value) };
+        self.porter.drop_bound(value);
+    }
+}
+
+/// Wakes the polling task when the value makes progress on the owner thread.
+struct WakePoller<T, S>(Arc<Shared<T, S>>)
+where
+    T: 'static;
+
+impl<T, S> Wake for WakePoller<T, S>
+where
+    T: 'static,
+    S: Send + 'static,
+{
+    fn wake(self: Arc<Self>) {
+        self.0.wake()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.wake()
+    }
+}
+
+type FutureShared<F> = Shared<Option<Pin<Box<F>>>, Option<<F as Future>::Output>>;
+
+/// A thread-bound future that is polled in place on its owner thread and
+/// defers polls from other threads.
+///
+/// Created by [`ThreadBound::into_lenient_future`].
+///
+/// This is intended for executors that may move tasks between threads at
+/// await points.
+/// When polled on the owner thread, the inner future is polled directly.
+/// When polled from another thread, this returns [`Poll::Pending`] and schedules
+/// a poll on the owner thread through its [`Porter`](crate::Porter) instead of panicking.
+/// Once the inner future makes progress, the polling task is woken again.
+///
+/// Dropping the adapter drops the inner future on its owner thread.
+///
+/// ### Panics
+/// Polling from another thread than the owner thread panics if the porter has been closed.
+#[must_use = "futures do nothing unless polled"]
+pub struct LenientFuture<F>
+where
+    F: Future + 'static,
+{
+    shared: Arc<FutureShared<F>>,
+}
+
+impl<F> LenientFuture<F>
+where
+    F: Future + 'static,
+    F::Output: Send + 'static,
+{
+    pub(crate) fn new(future: ThreadBound<F>, porter: PorterHandle) -> Self {
+        let future = ThreadBound::into_inner(future);
+        Self { shared: Shared::new(Some(Box::pin(future)), None, porter) }
+    }
+
+    fn poll_owner(future: &mut Option<Pin<Box<F>>>, cx: &mut Context) -> Poll<F::Output> {
+        let Some(fut) = future else { return Poll::Pending };
+        let output = std::task::ready!(fut.as_mut().poll(cx));
+        *future = None;
+        Poll::Ready(output)
+    }
+}
+
+impl<F> fmt::Debug for LenientFuture<F>
+where
+    F: Future + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LenientFuture").field("thread_id", &self.shared.porter.thread_id()).finish()
+    }
+}
+
+impl<F> Future for LenientFuture<F>
+where
+    F: Future + 'static,
+    F::Output: Send + 'static,
+{
+    type Output = F::Output;
+
+    #[track_caller]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<F::Output> {
+        let shared = &self.shared;
+
+        if let Some(output) = shared.state().take() {
+            return Poll::Ready(output);
+        }
+        shared.register(cx.waker());
+
+        if shared.is_owner() {
+            return Self::poll_owner(&mut shared.value.borrow_mut(), cx);
+        }
+
+        shared.schedule(|shared, future, cx| {
+            if let Poll::Ready(output) = Self::poll_owner(future, cx) {
+                *shared.state() = Some(output);
+                shared.wake();
+            }
+        });
+        Poll::Pending
+    }
+}
+
+struct StreamState<T> {
+    buffer: Option<T>,
+    done: bool,
+}
+
+type StreamShared<S> = Shared<Pin<Box<S>>, StreamState<<S as Stream>::Item>>;
+
+/// A thread-bound stream that is polled in place on its owner thread and
+/// defers polls from other threads.
+///
+/// Created by [`ThreadBound::into_lenient_stream`].
+///
+/// When polled on the owner thread, the inner stream is polled directly.
+/// When polled from another thread, this returns [`Poll::Pending`] and schedules
+/// a poll on the owner thread through its [`Porter`](crate::Porter), which moves
+/// the next item into a buffer and wakes the polling task.
+///
+/// The buffer holds at most one item and items are always yielded in the
+/// order produced by the inner stream.
+///
+/// Dropping the adapter drops the inner stream on its owner thread.
+///
+/// ### Panics
+/// Polling from another thread than the owner thread panics if the porter has been closed.
+#[must_use = "streams do nothing unless polled"]
+pub struct LenientStream<S>
+where
+    S: Stream + 'static,
+{
+    shared: Arc<StreamShared<S>>,
+}
+
+impl<S> LenientStream<S>
+where
+    S: Stream + 'static,
+    S::Item: Send + 'static,
+{
+    pub(crate) fn new(stream: ThreadBound<S>, porter: PorterHandle) -> Self {
+        let stream = ThreadBound::into_inner(stream);
+        Self { shared: Shared::new(Box::pin(stream), StreamState { buffer: None, done: false }, porter) }
+    }
+}
+
+impl<S> fmt::Debug for LenientStream<S>
+where
+    S: Stream + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LenientStream").field("thread_id", &self.shared.porter.thread_id()).finish()
+    }
+}
+
+impl<S> Stream for LenientStream<S>
+where
+    S: Stream + 'static,
+    S::Item: Send + 'static,
+{
+    type Item = S::Item;
+
+    #[track_caller]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<S::Item>> {
+        let shared = &self.shared;
+
+        {
+            let mut state = shared.state();
+            if let Some(item) = state.buffer.take() {
+                return Poll::Ready(Some(item));
+            }
+            if state.done {
+                return Poll::Ready(None);
+            }
+        }
+        shared.register(cx.waker());
+
+        if shared.is_owner() {
+            let item = std::task::ready!(shared.value.borrow_mut().as_mut().poll_next(cx));
+            if item.is_none() {
+                shared.state().done = true;
+            }
+            return Poll::Ready(item);
+        }
+
+        shared.schedule(|shared, stream, cx| {
+            if shared.state().buffer.is_some() {
+                return;
+            }
+            if let Poll::Ready(item) = stream.as_mut().poll_next(cx) {
+                let mut state = shared.state();
+                match item {
+                    Some(item) => state.buffer = Some(item),
+                    None => state.done = true,
+                }
+                drop(state);
+                shared.wake();
+            }
+        });
+        Poll::Pending
+    }
+}
+
+/// Operation requested from the owner thread of a sink.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Want {
+    Ready,
+    Flush,
+    Close,
+}
+
+struct SinkState<I, E> {
+    pending: Option<I>,
+    error: Option<E>,
+    flushed: bool,
+    closed: bool,
+    want: Want,
+}
+
+type SinkShared<Si, I> = Shared<Pin<Box<Si>>, SinkState<I, <Si as Sink<I>>::Error>>;
+
+/// A thread-bound sink that is driven in place on its owner thread and
+/// forwards use from other threads to it.
+///
+/// Created by [`ThreadBound::into_lenient_sink`].
+///
+/// When used on the owner thread, the inner sink is called directly.
+/// When used from another thread, items are buffered and handed to the
+/// owner thread through its [`Porter`](crate::Porter), which feeds them into
+/// the inner sink and wakes the sending task once it has made progress.
+///
+/// The buffer holds at most one item, so [`poll_ready`](Sink::poll_ready) waits
+/// until the previously sent item has been accepted by the inner sink.
+/// Items reach the inner sink in the order they were sent.
+/// Errors of the inner sink encountered while feeding buffered items are reported
+/// by the next call to `poll_ready`, `poll_flush` or `poll_close`.
+///
+/// Dropping the adapter drops the inner sink on its owner thread.
+///
+/// ### Panics
+/// Using the sink from another thread than the owner thread panics if the porter has been closed.
+#[must_use = "sinks do nothing unless polled"]
+pub struct LenientSink<Si, I>
+where
+    Si: Sink<I> + 'static,
+{
+    shared: Arc<SinkShared<Si, I>>,
+}
+
+impl<Si, I> LenientSink<Si, I>
+where
+    Si: Sink<I> + 'static,
+    Si::Error: Send + 'static,
+    I: Send + 'static,
+{
+    pub(crate) fn new(sink: ThreadBound<Si>, porter: PorterHandle) -> Self {
+        let sink = ThreadBound::into_inner(sink);
+        let state = SinkState { pending: None, error: None, flushed: false, closed: false, want: Want::Ready };
+        Self { shared: Shared::new(Box::pin(sink), state, porter) }
+    }
+
+    /// Feeds the buffered item into the inner sink and performs the requested operation.
+    fn drive(
+        shared: &SinkShared<Si, I>, sink: &mut Pin<Box<Si>>, want: Want, cx: &mut Context,
+    ) -> Poll<Result<(), Si::Error>> {
+        let pending = shared.state().pending.take();
+        if let Some(item) = pending {
+            match sink.as_mut().poll_ready(cx) {
+                Poll::Ready(Ok(())) => sink.as_mut().start_send(item)?,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => {
+                    shared.state().pending = Some(item);
+                    return Poll::Pending;
+                }
+            }
+        }
+
+        let result = std::task::ready!(match want {
+            Want::Ready => sink.as_mut().poll_ready(cx),
+            Want::Flush => sink.as_mut().poll_flush(cx),
+            Want::Close => sink.as_mut().poll_close(cx),
+        });
+
+        if result.is_ok() {
+            let mut state = shared.state();
+            match want {
+                Want::Ready => (),
+                Want::Flush => state.flushed = true,
+                Want::Close => {
+                    state.flushed = true;
+                    state.closed = true;
+                }
+            }
+        }
+        Poll::Ready(result)
+    }
+
+    /// Performs the operation, directly on the owner thread or by scheduling it there.
+    #[track_caller]
+    fn poll_want(&self, want: Want, cx: &mut Context) -> Poll<Result<(), Si::Error>> {
+        let shared = &self.shared;
+
+        {
+            let mut state = shared.state();
+            if let Some(err) = state.error.take() {
+                return Poll::Ready(Err(err));
+            }
+            let done = match want {
+                Want::Ready => state.pending.is_none() && !state.closed,
+                Want::Flush => state.pending.is_none() && state.flushed,
+                Want::Close => state.closed,
+            };
+            if done && !shared.is_owner() {
+                return Poll::Ready(Ok(()));
+            }
+        }
+        shared.register(cx.waker());
+
+        if shared.is_owner() {
+            return Self::drive(shared, &mut shared.value.borrow_mut(), want, cx);
+        }
+
+        {
+            let mut state = shared.state();
+            state.want = state.want.max(want);
+        }
+        shared.schedule(|shared, sink, cx| {
+            let want = std::mem::replace(&mut shared.state().want, Want::Ready);
+            match Self::drive(shared, sink, want, cx) {
+                Poll::Ready(result) => {
+                    if let Err(err) = result {
+                        shared.state().error = Some(err);
+                    }
+                    shared.wake();
+                }
+                Poll::Pending => (),
+            }
+        });
+        Poll::Pending
+    }
+}
+
+impl<Si, I> fmt::Debug for LenientSink<Si, I>
+where
+    Si: Sink<I> + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LenientSink").field("thread_id", &self.shared.porter.thread_id()).finish()
+    }
+}
+
+impl<Si, I> Sink<I> for LenientSink<Si, I>
+where
+    Si: Sink<I> + 'static,
+    Si::Error: Send + 'static,
+    I: Send + 'static,
+{
+    type Error = Si::Error;
+
+    #[track_caller]
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.poll_want(Want::Ready, cx)
+    }
+
+    #[track_caller]
+    fn start_send(self: Pin<&mut Self>, item: I) -> Result<(), Self::Error> {
+        let shared = &self.shared;
+
+        let mut state = shared.state();
+        if let Some(err) = state.error.take() {
+            return Err(err);
+        }
+        state.flushed = false;
+
+        if shared.is_owner() && state.pending.is_none() {
+            drop(state);
+            return shared.value.borrow_mut().as_mut().start_send(item);
+        }
+
+        assert!(state.pending.is_none(), "start_send called without poll_ready");
+        state.pending = Some(item);
+        drop(state);
+
+        if !shared.is_owner() {
+            shared.schedule(|shared, sink, cx| {
+                if let Poll::Ready(Err(err)) = Self::drive(shared, sink, Want::Ready, cx) {
+                    shared.state().error = Some(err);
+                }
+                shared.wake();
+            });
+        }
+        Ok(())
+    }
+
+    #[track_caller]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.poll_want(Want::Flush, cx)
+    }
+
+    #[track_caller]
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.poll_want(Want::Close, cx)
+    }
+}
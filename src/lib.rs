@@ -5,5 +5,91 @@
 //! WebAssembly and working with JavaScript objects.
 //!
 
+#![cfg_attr(feature = "nightly", feature(arbitrary_self_types))]
+
+mod actor;
+mod arena;
+mod boxed;
+mod callback;
+mod cancel;
+mod cell;
+mod channel;
+mod deferred;
+mod dispatched;
+mod ext;
+mod frozen;
+mod group;
+mod guard;
+mod lenient;
+#[cfg(feature = "diagnostics")]
+mod live;
+mod liveness;
+mod lock;
+mod map;
+mod meta;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod oneshot;
+mod policy;
+mod pool;
+mod porter;
+mod projection;
+mod rebuild;
+mod registry;
+mod scoped;
+mod shared;
+mod snapshot;
+mod spawn;
+mod ssr;
 mod thread_bound;
-pub use thread_bound::{thread_bound, ThreadBound};
+mod timer;
+#[cfg(feature = "diagnostics")]
+mod trace;
+mod waker;
+
+#[doc(hidden)]
+pub use actor::RpcClient;
+pub use actor::{BoundActor, Mailbox};
+pub use arena::{ArenaId, BoundArena};
+pub use boxed::{boxed_send_local, boxed_send_local_stream, SendLocalBoxFuture, SendLocalBoxStream};
+pub use callback::{BoundCallback, BoundCallbackMut};
+pub use cancel::CancelToken;
+pub use cell::BoundBorrowError;
+pub use channel::{bound_channel, bound_channel_with_policy, BoundReceiver, BoundSender};
+#[cfg(not(target_arch = "wasm32"))]
+pub use deferred::flush_deferred;
+pub use deferred::{deferred_len, flush_deferred_all, flush_deferred_count, flush_deferred_until};
+pub use dispatched::DispatchedFuture;
+pub use ext::{BoundItems, BoundIteratorExt, BoundStreamExt, MapBound, ThreadBoundExt};
+pub use frozen::FrozenBound;
+pub use group::{BoundGroup, BoundTuple};
+pub use guard::{BoundGuard, BoundGuardMut, ScopeTake};
+pub use lenient::{LenientFuture, LenientSink, LenientStream};
+#[cfg(feature = "diagnostics")]
+pub use live::{dump_live, track_live, LiveBound};
+pub use lock::{BoundLock, BoundLockGuard};
+pub use map::BoundMap;
+pub use meta::MetaBound;
+#[cfg(feature = "metrics")]
+pub use metrics::LatencyHistogram;
+pub use policy::{Leaky, Lenient, Panicking, Policy, Strict};
+pub use pool::{BoundPool, Placement, Pooled};
+pub use porter::{
+    spawn_on_owner, try_spawn_on_owner, Batch, Call, CallError, Permit, Porter, PorterHandle, Pumped, Reserve,
+    Shutdown, TryCall,
+};
+pub use projection::SendProjection;
+pub use rebuild::RebuildableBound;
+pub use registry::{BoundHandle, BoundRegistry, StaleHandle};
+pub use scoped::{scoped_thread_bound, ScopedBound};
+pub use shared::SharedBound;
+pub use snapshot::{thread_bound_with_snapshot, DisplayBound, SnapshotBound};
+pub use spawn::{spawn_local_bound_with, JoinError, JoinHandle};
+pub use ssr::SsrSafe;
+pub use thread_bound::{
+    bound_mut, bound_ref, thread_bound, thread_bound_named, thread_bound_pin, BoundMut, BoundRef, DropContext,
+    ThreadBound, WrongThread,
+};
+#[cfg(feature = "diagnostics")]
+pub use trace::Access;
+pub use waker::BoundWaker;
@@ -0,0 +1,91 @@
+//! Registry of live thread-bound values for leak hunting.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    panic::Location,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex, MutexGuard, OnceLock, PoisonError,
+    },
+    thread::ThreadId,
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A live thread-bound value listed by [`dump_live`].
+#[derive(Clone, Debug)]
+pub struct LiveBound {
+    /// The type of the value.
+    pub type_name: &'static str,
+    /// The label of the value.
+    pub label: Option<&'static str>,
+    /// The owner thread of the value.
+    pub owner: ThreadId,
+    /// The location of the code that bound the value.
+    pub location: &'static Location<'static>,
+}
+
+impl fmt::Display for LiveBound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.type_name)?;
+        if let Some(label) = self.label {
+            write!(f, " ({label})")?;
+        }
+        write!(f, " on thread {:?} bound at {}", self.owner, self.location)
+    }
+}
+
+/// The live values by registration id.
+fn live() -> MutexGuard<'static, HashMap<u64, LiveBound>> {
+    static LIVE: OnceLock<Mutex<HashMap<u64, LiveBound>>> = OnceLock::new();
+    LIVE.get_or_init(Mutex::default).lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Enables or disables the registration of thread-bound values created from now on
+/// for [`dump_live`].
+///
+/// Registration is disabled by default, since it adds overhead to creating and
+/// dropping thread-bound values.
+/// Disabling it does not unregister values that are still live.
+pub fn track_live(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Lists the registered thread-bound values that are still live, ordered by
+/// owner thread and creation.
+///
+/// Values are only registered while [`track_live`] is enabled.
+/// This is useful for leak hunting and shutdown audits.
+pub fn dump_live() -> Vec<LiveBound> {
+    let live = live();
+    let mut entries: Vec<_> = live.iter().collect();
+    entries.sort_by_key(|(id, _)| **id);
+
+    // Group by owner thread in order of the first value created on each thread.
+    let mut threads = HashMap::new();
+    for (_, entry) in &entries {
+        let next = threads.len();
+        threads.entry(entry.owner).or_insert(next);
+    }
+    entries.sort_by_key(|(_, entry)| threads[&entry.owner]);
+
+    entries.into_iter().map(|(_, entry)| entry.clone()).collect()
+}
+
+/// Registers a live value if tracking is enabled.
+pub(crate) fn register(entry: impl FnOnce() -> LiveBound) -> Option<u64> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    live().insert(id, entry());
+    Some(id)
+}
+
+/// Unregisters a value that is no longer live.
+pub(crate) fn unregister(id: u64) {
+    live().remove(&id);
+}
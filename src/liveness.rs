@@ -0,0 +1,103 @@
+//! Tracking of owner thread termination.
+
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, OnceLock, PoisonError},
+    thread,
+    thread::ThreadId,
+};
+
+#[derive(Default)]
+struct State {
+    /// The threads that have exited with the number of bindings still owned by them.
+    exited: HashMap<ThreadId, usize>,
+    /// Changes to the number of bindings of live threads made on other threads.
+    foreign: HashMap<ThreadId, isize>,
+}
+
+impl State {
+    fn adjust(&mut self, owner: ThreadId, delta: isize, exiting: bool) {
+        if let Some(live) = self.exited.get_mut(&owner) {
+            *live = live.wrapping_add_signed(delta);
+            if *live == 0 {
+                self.exited.remove(&owner);
+            }
+        } else if exiting {
+            if let Ok(live) = usize::try_from(delta) {
+                self.exited.insert(owner, live);
+            }
+        } else {
+            let live = self.foreign.entry(owner).or_default();
+            *live += delta;
+            if *live == 0 {
+                self.foreign.remove(&owner);
+            }
+        }
+    }
+}
+
+fn state() -> MutexGuard<'static, State> {
+    static STATE: OnceLock<Mutex<State>> = OnceLock::new();
+    STATE.get_or_init(Mutex::default).lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Counts the bindings owned by its thread and marks the thread as exited
+/// when the thread-local storage is destroyed.
+///
+/// An exited thread is only remembered while bindings owned by it remain.
+struct Alive {
+    id: ThreadId,
+    live: Cell<usize>,
+}
+
+impl Drop for Alive {
+    fn drop(&mut self) {
+        let mut state = state();
+        let foreign = state.foreign.remove(&self.id).unwrap_or_default();
+        let live = self.live.get().wrapping_add_signed(foreign);
+        if live > 0 {
+            state.exited.insert(self.id, live);
+        }
+    }
+}
+
+thread_local! {
+    static ALIVE: Alive = Alive { id: thread::current().id(), live: Cell::new(0) };
+}
+
+fn adjust(owner: ThreadId, delta: isize) {
+    let local = ALIVE.try_with(|alive| {
+        if alive.id != owner {
+            return false;
+        }
+        alive.live.set(alive.live.get().wrapping_add_signed(delta));
+        true
+    });
+    match local {
+        Ok(true) => (),
+        Ok(false) => state().adjust(owner, delta, false),
+        // The thread-local storage is being destroyed, so the current thread is exiting.
+        Err(_) => {
+            let exiting = thread::current().id() == owner;
+            state().adjust(owner, delta, exiting)
+        }
+    }
+}
+
+/// Registers a binding owned by the thread.
+#[inline]
+pub(crate) fn acquire(owner: ThreadId) {
+    adjust(owner, 1)
+}
+
+/// Unregisters a binding owned by the thread.
+#[inline]
+pub(crate) fn release(owner: ThreadId) {
+    adjust(owner, -1)
+}
+
+/// Whether the thread owning a live binding has exited.
+pub(crate) fn has_exited(thread_id: ThreadId) -> bool {
+    state().exited.contains_key(&thread_id)
+}
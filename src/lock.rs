@@ -0,0 +1,195 @@
+//! Owner-checked exclusive locks with poisoning.
+
+use std::{
+    cell::{Cell, RefCell, RefMut},
+    fmt,
+    ops::{Deref, DerefMut},
+    panic::{RefUnwindSafe, UnwindSafe},
+    sync::{LockResult, PoisonError, TryLockError, TryLockResult},
+    thread,
+    thread::ThreadId,
+};
+
+use crate::ThreadBound;
+
+struct State<T> {
+    value: RefCell<T>,
+    poisoned: Cell<bool>,
+}
+
+/// A value bound to a thread that can be mutated through a shared reference
+/// on the owner thread, like a [`Mutex`](std::sync::Mutex).
+///
+/// Locking checks that the current thread is the owner thread and returns a `!Send`
+/// guard, so that the lock can be stored in shared state, for example in an [`Arc`](std::sync::Arc),
+/// without nesting a [`RefCell`] within a [`ThreadBound`].
+///
+/// If a thread panics while holding the lock, the lock is poisoned
+/// and further locking returns a [`PoisonError`].
+///
+/// ### Panics
+/// Panics if the lock is accessed in any way from another thread
+/// (including dropping if the value needs drop).
+pub struct BoundLock<T> {
+    state: ThreadBound<State<T>>,
+}
+
+impl<T> BoundLock<T> {
+    /// Binds the value to the current thread.
+    #[track_caller]
+    pub fn new(value: T) -> Self {
+        Self { state: ThreadBound::new(State { value: RefCell::new(value), poisoned: Cell::new(false) }) }
+    }
+
+    /// The id of the thread that is allowed to lock the value.
+    pub fn thread_id(&self) -> ThreadId {
+        ThreadBound::thread_id(&self.state)
+    }
+
+    /// Whether the lock is usable from the current thread.
+    pub fn is_usable(&self) -> bool {
+        ThreadBound::is_usable(&self.state)
+    }
+
+    /// Acquires exclusive access to the value.
+    ///
+    /// Returns a [`PoisonError`] holding the guard if the lock is poisoned.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread or if the
+    /// lock is already held, since waiting for it would deadlock.
+    #[track_caller]
+    pub fn lock(&self) -> LockResult<BoundLockGuard<'_, T>> {
+        match self.try_lock() {
+            Ok(guard) => Ok(guard),
+            Err(TryLockError::Poisoned(err)) => Err(err),
+            Err(TryLockError::WouldBlock) => panic!("BoundLock is already locked by the owner thread"),
+        }
+    }
+
+    /// Acquires exclusive access to the value, if the lock is not already held.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn try_lock(&self) -> TryLockResult<BoundLockGuard<'_, T>> {
+        let state = &*self.state;
+        let value = state.value.try_borrow_mut().map_err(|_| TryLockError::WouldBlock)?;
+        let guard = BoundLockGuard { value, poisoned: &state.poisoned, panicking: thread::panicking() };
+        match state.poisoned.get() {
+            false => Ok(guard),
+            true => Err(TryLockError::Poisoned(PoisonError::new(guard))),
+        }
+    }
+
+    /// Whether the lock is poisoned.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn is_poisoned(&self) -> bool {
+        self.state.poisoned.get()
+    }
+
+    /// Clears the poisoned state of the lock.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn clear_poison(&self) {
+        self.state.poisoned.set(false);
+    }
+
+    /// A mutable reference to the value.
+    ///
+    /// Since this borrows the lock mutably, no locking is needed.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn get_mut(&mut self) -> LockResult<&mut T> {
+        let state = &mut *self.state;
+        let value = state.value.get_mut();
+        match state.poisoned.get() {
+            false => Ok(value),
+            true => Err(PoisonError::new(value)),
+        }
+    }
+
+    /// Takes the value out.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn into_inner(self) -> LockResult<T> {
+        let state = ThreadBound::into_inner(self.state);
+        let value = state.value.into_inner();
+        match state.poisoned.get() {
+            false => Ok(value),
+            true => Err(PoisonError::new(value)),
+        }
+    }
+}
+
+// Like a mutex, the lock is poisoned instead of exposing a broken invariant after a panic.
+impl<T> UnwindSafe for BoundLock<T> {}
+impl<T> RefUnwindSafe for BoundLock<T> {}
+
+impl<T> Default for BoundLock<T>
+where
+    T: Default,
+{
+    #[track_caller]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> fmt::Debug for BoundLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundLock").field("thread_id", &self.thread_id()).finish_non_exhaustive()
+    }
+}
+
+/// Exclusive access to the value of a [`BoundLock`].
+///
+/// The guard is `!Send`, so that a future holding it across an `.await` is
+/// `!Send` as well and cannot be moved to another thread by a work-stealing executor.
+/// Dropping the guard because of a panic poisons the lock.
+#[must_use = "the guard grants access until it is dropped"]
+pub struct BoundLockGuard<'a, T> {
+    value: RefMut<'a, T>,
+    poisoned: &'a Cell<bool>,
+    panicking: bool,
+}
+
+impl<T> Deref for BoundLockGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for BoundLockGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T> Drop for BoundLockGuard<'_, T> {
+    fn drop(&mut self) {
+        if !self.panicking && thread::panicking() {
+            self.poisoned.set(true);
+        }
+    }
+}
+
+impl<T> fmt::Debug for BoundLockGuard<'_, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
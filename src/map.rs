@@ -0,0 +1,190 @@
+//! Map of `!Send` values living on an owner thread, usable from any thread.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::Hash,
+    mem::ManuallyDrop,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{Call, PorterHandle, ThreadBound};
+
+struct Inner<K, V>
+where
+    K: 'static,
+    V: 'static,
+{
+    values: ManuallyDrop<ThreadBound<RefCell<HashMap<K, V>>>>,
+    keys: Mutex<HashSet<K>>,
+    porter: PorterHandle,
+}
+
+impl<K, V> Inner<K, V>
+where
+    K: 'static,
+    V: 'static,
+{
+    fn keys(&self) -> MutexGuard<'_, HashSet<K>> {
+        self.keys.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<K, V> Drop for Inner<K, V>
+where
+    K: 'static,
+    V: 'static,
+{
+    fn drop(&mut self) {
+        let values = unsafe { ManuallyDrop::take(&mut self.values) };
+        self.porter.drop_bound(values);
+    }
+}
+
+/// A map of `!Send` values living on the thread of a porter, which can be used from any thread.
+///
+/// Values are constructed, accessed and removed on the owner thread by its porter,
+/// while the keys are tracked on the side, so that [`contains_key`](Self::contains_key)
+/// and [`len`](Self::len) can be answered from any thread.
+/// The tracked keys reflect the insertions and removals executed by the porter so far.
+///
+/// Operations are always queued and executed by the porter in order of submission,
+/// even when called from the owner thread.
+/// Each operation is executed even if the future it returns is dropped;
+/// the future only reports its completion and result.
+///
+/// The map is [`Send`] + [`Sync`] and can be cloned freely; clones refer to the same map.
+/// The values are dropped on the owner thread once the last clone is gone.
+///
+/// ### Panics
+/// The operations panic if the porter has been dropped.
+/// Awaiting the futures they return panics if the porter is dropped before the
+/// operation has been executed.
+pub struct BoundMap<K, V>
+where
+    K: 'static,
+    V: 'static,
+{
+    inner: Arc<Inner<K, V>>,
+}
+
+impl<K, V> BoundMap<K, V>
+where
+    K: Eq + Hash + Clone + Send + 'static,
+    V: 'static,
+{
+    /// Creates an empty map on the current thread, whose values are managed by the porter.
+    ///
+    /// ### Panics
+    /// Panics if the porter does not belong to the current thread.
+    #[track_caller]
+    pub fn new(porter: &PorterHandle) -> Self {
+        assert!(porter.is_owner(), "porter must belong to the current thread");
+        let inner = Inner {
+            values: ManuallyDrop::new(ThreadBound::new(RefCell::new(HashMap::new()))),
+            keys: Mutex::new(HashSet::new()),
+            porter: porter.clone(),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// The porter managing the values.
+    pub fn porter(&self) -> &PorterHandle {
+        &self.inner.porter
+    }
+
+    /// Constructs a value on the owner thread using the [`Send`] factory and inserts it.
+    ///
+    /// A value previously stored under the key is dropped on the owner thread.
+    /// The returned future resolves once the value has been inserted.
+    #[track_caller]
+    pub fn insert_with<F>(&self, key: K, factory: F) -> Call<()>
+    where
+        F: FnOnce() -> V + Send + 'static,
+    {
+        let inner = self.inner.clone();
+        self.inner.porter.call_detached(move || {
+            let value = factory();
+            inner.keys().insert(key.clone());
+            // The previous value is dropped after releasing the borrow, since its drop may use the map.
+            let previous = inner.values.borrow_mut().insert(key, value);
+            drop(previous);
+        })
+    }
+
+    /// Calls `f` with the value of the key on the owner thread and returns its result,
+    /// or `None` if the map does not contain the key.
+    ///
+    /// The values are borrowed while `f` runs, so `f` must not access the same map.
+    #[track_caller]
+    pub fn with<F, R>(&self, key: K, f: F) -> Call<Option<R>>
+    where
+        F: FnOnce(&mut V) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let inner = self.inner.clone();
+        self.inner.porter.call_detached(move || inner.values.borrow_mut().get_mut(&key).map(f))
+    }
+
+    /// Removes the value of the key and drops it on the owner thread.
+    ///
+    /// The returned future resolves to whether the map contained the key.
+    #[track_caller]
+    pub fn remove(&self, key: K) -> Call<bool> {
+        let inner = self.inner.clone();
+        self.inner.porter.call_detached(move || {
+            inner.keys().remove(&key);
+            let value = inner.values.borrow_mut().remove(&key);
+            value.is_some()
+        })
+    }
+
+    /// Whether the map contains the key.
+    ///
+    /// This can be called from any thread.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.inner.keys().contains(key)
+    }
+
+    /// The keys of the map.
+    ///
+    /// This can be called from any thread.
+    pub fn keys(&self) -> Vec<K> {
+        self.inner.keys().iter().cloned().collect()
+    }
+
+    /// The number of values in the map.
+    ///
+    /// This can be called from any thread.
+    pub fn len(&self) -> usize {
+        self.inner.keys().len()
+    }
+
+    /// Whether the map contains no values.
+    ///
+    /// This can be called from any thread.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K, V> Clone for BoundMap<K, V>
+where
+    K: 'static,
+    V: 'static,
+{
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<K, V> fmt::Debug for BoundMap<K, V>
+where
+    K: 'static,
+    V: 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundMap").field("thread_id", &self.inner.porter.thread_id()).finish_non_exhaustive()
+    }
+}
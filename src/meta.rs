@@ -0,0 +1,90 @@
+//! Thread-bound values carrying metadata readable from any thread.
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use crate::ThreadBound;
+
+/// A thread-bound value together with metadata that is accessible from any thread.
+///
+/// The metadata is supplied when binding, for example a routing key, a resource id
+/// or a creation timestamp, and travels along with the bound value.
+///
+/// Created by [`ThreadBound::with_meta`] or [`ThreadBoundExt::bound_with_meta`](crate::ThreadBoundExt::bound_with_meta).
+///
+/// The bound value is accessible through [`Deref`] to [`ThreadBound`].
+/// The [debug representation](fmt::Debug) includes the metadata and can be
+/// safely used from any thread.
+pub struct MetaBound<T, M> {
+    bound: ThreadBound<T>,
+    meta: M,
+}
+
+impl<T, M> MetaBound<T, M> {
+    /// Binds the value to the current thread and attaches the metadata.
+    pub fn new(value: T, meta: M) -> Self {
+        Self::from_parts(ThreadBound::new(value), meta)
+    }
+
+    pub(crate) fn from_parts(bound: ThreadBound<T>, meta: M) -> Self {
+        Self { bound, meta }
+    }
+
+    /// The metadata.
+    ///
+    /// This can be called from any thread.
+    pub fn meta(this: &Self) -> &M {
+        &this.meta
+    }
+
+    /// The metadata mutably.
+    ///
+    /// This can be called from any thread.
+    pub fn meta_mut(this: &mut Self) -> &mut M {
+        &mut this.meta
+    }
+
+    /// Splits into the bound value and the metadata.
+    pub fn into_parts(this: Self) -> (ThreadBound<T>, M) {
+        (this.bound, this.meta)
+    }
+}
+
+impl<T, M> Deref for MetaBound<T, M> {
+    type Target = ThreadBound<T>;
+
+    fn deref(&self) -> &ThreadBound<T> {
+        &self.bound
+    }
+}
+
+impl<T, M> DerefMut for MetaBound<T, M> {
+    fn deref_mut(&mut self) -> &mut ThreadBound<T> {
+        &mut self.bound
+    }
+}
+
+impl<T, M> Clone for MetaBound<T, M>
+where
+    T: Clone,
+    M: Clone,
+{
+    #[track_caller]
+    fn clone(&self) -> Self {
+        Self { bound: self.bound.clone(), meta: self.meta.clone() }
+    }
+}
+
+impl<T, M> fmt::Debug for MetaBound<T, M>
+where
+    M: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MetaBound")
+            .field("thread_id", &ThreadBound::thread_id(&self.bound))
+            .field("meta", &self.meta)
+            .finish()
+    }
+}
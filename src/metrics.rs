@@ -0,0 +1,75 @@
+//! Latency metrics of porters.
+
+use std::{fmt, time::Duration};
+
+const BUCKETS: usize = 32;
+
+/// Histogram of the latencies of closures executed by a [`Porter`](crate::Porter),
+/// measured from submission to completion.
+///
+/// Latencies are counted in buckets whose upper bounds are powers of two microseconds,
+/// starting at one microsecond.
+/// Obtained from [`PorterHandle::latency`](crate::PorterHandle::latency).
+#[derive(Clone, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    counts: [u64; BUCKETS],
+}
+
+impl LatencyHistogram {
+    pub(crate) fn new() -> Self {
+        Self { counts: [0; BUCKETS] }
+    }
+
+    pub(crate) fn record(&mut self, latency: Duration) {
+        let micros = latency.as_micros();
+        let bucket = match micros {
+            0 => 0,
+            micros => (u128::BITS - (micros - 1).leading_zeros()) as usize,
+        };
+        self.counts[bucket.min(BUCKETS - 1)] += 1;
+    }
+
+    /// The number of recorded latencies.
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// The upper bound of each bucket together with the number of latencies recorded in it.
+    ///
+    /// The last bucket also counts all latencies exceeding its bound.
+    pub fn buckets(&self) -> impl Iterator<Item = (Duration, u64)> + '_ {
+        self.counts.iter().enumerate().map(|(n, count)| (Self::bound(n), *count))
+    }
+
+    /// An upper bound of the latency below which the fraction `q` of recorded latencies fall,
+    /// for example `0.99` for the 99th percentile.
+    ///
+    /// Returns `None` if no latencies have been recorded.
+    pub fn quantile(&self, q: f64) -> Option<Duration> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+
+        let target = ((count as f64 * q.clamp(0.0, 1.0)).ceil() as u64).max(1);
+        let mut seen = 0;
+        self.counts.iter().enumerate().find_map(|(n, c)| {
+            seen += c;
+            (seen >= target).then(|| Self::bound(n))
+        })
+    }
+
+    fn bound(bucket: usize) -> Duration {
+        Duration::from_micros(1 << bucket)
+    }
+}
+
+impl fmt::Debug for LatencyHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut map = f.debug_map();
+        for (bound, count) in self.buckets().filter(|(_, count)| *count > 0) {
+            map.entry(&bound, &count);
+        }
+        map.finish()
+    }
+}
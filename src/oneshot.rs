@@ -0,0 +1,101 @@
+//! Single-use channel delivering the result of a remote call.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
+};
+
+struct Inner<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+    closed: bool,
+    canceled: bool,
+}
+
+struct Chan<T>(Mutex<Inner<T>>);
+
+impl<T> Chan<T> {
+    fn lock(&self) -> MutexGuard<'_, Inner<T>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Sending half of a oneshot channel.
+pub(crate) struct Sender<T> {
+    chan: Arc<Chan<T>>,
+}
+
+/// Receiving half of a oneshot channel.
+pub(crate) struct Receiver<T> {
+    chan: Arc<Chan<T>>,
+}
+
+/// The sender was dropped without sending a value.
+#[derive(Debug)]
+pub(crate) struct Closed;
+
+/// Creates a new oneshot channel.
+pub(crate) fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let chan = Arc::new(Chan(Mutex::new(Inner { value: None, waker: None, closed: false, canceled: false })));
+    (Sender { chan: chan.clone() }, Receiver { chan })
+}
+
+impl<T> Sender<T> {
+    /// Sends the value and wakes the receiver.
+    pub(crate) fn send(self, value: T) {
+        self.chan.lock().value = Some(value);
+    }
+
+    /// Whether the receiver has been dropped.
+    pub(crate) fn is_canceled(&self) -> bool {
+        self.chan.lock().canceled
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut inner = self.chan.lock();
+            inner.closed = true;
+            inner.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    /// A receiver that is already resolved with the value.
+    pub(crate) fn ready(value: T) -> Self {
+        let inner = Inner { value: Some(value), waker: None, closed: true, canceled: false };
+        Self { chan: Arc::new(Chan(Mutex::new(inner))) }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.chan.lock().canceled = true;
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = Result<T, Closed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let mut inner = self.chan.lock();
+        if let Some(value) = inner.value.take() {
+            return Poll::Ready(Ok(value));
+        }
+        if inner.closed {
+            return Poll::Ready(Err(Closed));
+        }
+        match &mut inner.waker {
+            Some(waker) => waker.clone_from(cx.waker()),
+            None => inner.waker = Some(cx.waker().clone()),
+        }
+        Poll::Pending
+    }
+}
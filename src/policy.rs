@@ -0,0 +1,60 @@
+//! Policies for handling accesses from foreign threads.
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// How a [`ThreadBound`](crate::ThreadBound) handles the use of its value from another thread.
+///
+/// This trait is sealed; the available policies are [`Strict`], [`Lenient`] and [`Leaky`].
+pub trait Policy: sealed::Sealed + 'static {
+    /// Whether a value dropped on another thread is leaked instead of causing a panic.
+    #[doc(hidden)]
+    const LEAK_FOREIGN_DROP: bool;
+}
+
+/// A [`Policy`] under which accessing the value from another thread panics.
+///
+/// Only thread-bound values using such a policy provide panicking accessors,
+/// such as [`Deref`](std::ops::Deref).
+pub trait Panicking: Policy {}
+
+/// Panics when the value is accessed or dropped on another thread.
+///
+/// This is the default policy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Strict;
+
+impl sealed::Sealed for Strict {}
+
+impl Policy for Strict {
+    const LEAK_FOREIGN_DROP: bool = false;
+}
+
+impl Panicking for Strict {}
+
+/// Never panics: accessors return a [`Result`] and a value dropped on another thread is leaked.
+///
+/// The value is accessed using [`ThreadBound::get`](crate::ThreadBound::get),
+/// [`ThreadBound::get_mut`](crate::ThreadBound::get_mut) and the `try_` methods.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Lenient;
+
+impl sealed::Sealed for Lenient {}
+
+impl Policy for Lenient {
+    const LEAK_FOREIGN_DROP: bool = true;
+}
+
+/// Panics when the value is accessed on another thread, but leaks it when it is
+/// dropped on another thread.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Leaky;
+
+impl sealed::Sealed for Leaky {}
+
+impl Policy for Leaky {
+    const LEAK_FOREIGN_DROP: bool = true;
+}
+
+impl Panicking for Leaky {}
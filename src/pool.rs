@@ -0,0 +1,306 @@
+//! A pool of owner threads hosting `!Send` values.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    mem::ManuallyDrop,
+    pin::pin,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc, Arc, Mutex, OnceLock, PoisonError,
+    },
+    task::{Context, Wake, Waker},
+    thread,
+    thread::{JoinHandle, Thread, ThreadId},
+};
+
+use crate::{Call, Porter, PorterHandle, ThreadBound};
+
+/// How a [`BoundPool`] chooses the thread for a new value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Placement {
+    /// Cycle through the threads.
+    #[default]
+    RoundRobin,
+    /// Pick the thread hosting the fewest live values.
+    LeastLoaded,
+}
+
+struct Unpark(Thread);
+
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark()
+    }
+}
+
+struct Worker {
+    porter: PorterHandle,
+    load: Arc<AtomicUsize>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// A pool of dedicated owner threads that host `!Send` values.
+///
+/// Each thread runs a [`Porter`], which is [registered](Porter::register) for it.
+/// Values are constructed on one of the threads according to the [`Placement`]
+/// and accessed through [`Pooled`] handles, which are [`Send`] + [`Sync`].
+///
+/// Dropping the pool stops its threads once they have executed the closures
+/// submitted so far.
+/// Handles outliving the pool can no longer be used and their values are leaked.
+pub struct BoundPool {
+    workers: Vec<Worker>,
+    placement: Placement,
+    next: AtomicUsize,
+    stop: Arc<AtomicBool>,
+}
+
+impl BoundPool {
+    /// Spawns `threads` owner threads using round-robin placement.
+    ///
+    /// ### Panics
+    /// Panics if `threads` is zero or a thread cannot be spawned.
+    pub fn new(threads: usize) -> Self {
+        Self::with_placement(threads, Placement::default())
+    }
+
+    /// Spawns `threads` owner threads using the specified placement.
+    ///
+    /// ### Panics
+    /// Panics if `threads` is zero or a thread cannot be spawned.
+    pub fn with_placement(threads: usize, placement: Placement) -> Self {
+        assert!(threads > 0, "pool must have at least one thread");
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let workers = (0..threads)
+            .map(|n| {
+                let (tx, rx) = mpsc::channel();
+                let stop = stop.clone();
+                let thread = thread::Builder::new()
+                    .name(format!("threadporter-pool-{n}"))
+                    .spawn(move || {
+                        let porter = Porter::new();
+                        porter.register();
+                        tx.send(porter.handle()).unwrap();
+                        Self::host(&porter, &stop);
+                    })
+                    .expect("cannot spawn pool thread");
+                Worker { porter: rx.recv().unwrap(), load: Arc::new(AtomicUsize::new(0)), thread: Some(thread) }
+            })
+            .collect();
+
+        Self { workers, placement, next: AtomicUsize::new(0), stop }
+    }
+
+    /// Drives the porter until the pool is stopped.
+    fn host(porter: &Porter, stop: &AtomicBool) {
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut run = pin!(porter.run());
+        loop {
+            let _ = run.as_mut().poll(&mut cx);
+            if stop.load(Ordering::Acquire) {
+                porter.run_until_idle();
+                break;
+            }
+            thread::park();
+        }
+    }
+
+    /// The number of threads in the pool.
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// The placement used for new values.
+    pub fn placement(&self) -> Placement {
+        self.placement
+    }
+
+    fn pick(&self) -> &Worker {
+        match self.placement {
+            Placement::RoundRobin => {
+                &self.workers[self.next.fetch_add(1, Ordering::Relaxed) % self.workers.len()]
+            }
+            Placement::LeastLoaded => {
+                self.workers.iter().min_by_key(|worker| worker.load.load(Ordering::Relaxed)).unwrap()
+            }
+        }
+    }
+
+    /// Constructs a value on one of the threads of the pool using `init`.
+    ///
+    /// This returns immediately; calls made through the handle are executed
+    /// after the value has been constructed.
+    /// A call made from the hosting thread before the queued construction has run
+    /// constructs the value inline.
+    pub fn place<T, F>(&self, init: F) -> Pooled<T>
+    where
+        T: 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let worker = self.pick();
+        worker.load.fetch_add(1, Ordering::Relaxed);
+
+        let inner = Arc::new(PooledInner {
+            value: ManuallyDrop::new(OnceLock::new()),
+            init: Mutex::new(Some(Box::new(init))),
+            porter: worker.porter.clone(),
+            load: worker.load.clone(),
+        });
+        let target = inner.clone();
+        worker.porter.post(move || target.construct());
+
+        Pooled { inner }
+    }
+}
+
+impl fmt::Debug for BoundPool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundPool")
+            .field("threads", &self.workers.len())
+            .field("placement", &self.placement)
+            .finish()
+    }
+}
+
+impl Drop for BoundPool {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        for worker in &mut self.workers {
+            if let Some(thread) = worker.thread.take() {
+                thread.thread().unpark();
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+type Slot<T> = OnceLock<ThreadBound<RefCell<T>>>;
+
+type Init<T> = Box<dyn FnOnce() -> T + Send>;
+
+struct PooledInner<T>
+where
+    T: 'static,
+{
+    value: ManuallyDrop<Slot<T>>,
+    init: Mutex<Option<Init<T>>>,
+    porter: PorterHandle,
+    load: Arc<AtomicUsize>,
+}
+
+impl<T> PooledInner<T>
+where
+    T: 'static,
+{
+    /// Constructs the value on the hosting thread, unless this has already happened.
+    fn construct(&self) {
+        let init = self.init.lock().unwrap_or_else(PoisonError::into_inner).take();
+        if let Some(init) = init {
+            let _ = self.value.set(ThreadBound::new(RefCell::new(init())));
+        }
+    }
+
+    #[track_caller]
+    fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        self.construct();
+        let value = self.value.get().expect("pooled value has not been constructed");
+        let mut value = value.try_borrow_mut().expect("pooled value is already in use");
+        f(&mut value)
+    }
+}
+
+impl<T> Drop for PooledInner<T>
+where
+    T: 'static,
+{
+    fn drop(&mut self) {
+        self.load.fetch_sub(1, Ordering::Relaxed);
+        let slot = unsafe { ManuallyDrop::take(&mut self.value) };
+        if let Some(value) = slot.into_inner() {
+            self.porter.drop_bound(value);
+        }
+    }
+}
+
+/// Handle to a value hosted by a [`BoundPool`].
+///
+/// The handle is [`Send`] + [`Sync`] and can be cloned freely.
+/// The value is dropped on its thread once the last handle is gone.
+pub struct Pooled<T>
+where
+    T: 'static,
+{
+    inner: Arc<PooledInner<T>>,
+}
+
+impl<T> Pooled<T>
+where
+    T: 'static,
+{
+    /// The id of the thread hosting the value.
+    pub fn thread_id(&self) -> ThreadId {
+        self.inner.porter.thread_id()
+    }
+
+    /// The porter of the thread hosting the value.
+    pub fn porter(&self) -> &PorterHandle {
+        &self.inner.porter
+    }
+
+    /// Executes the closure with the value on its thread without waiting for the result.
+    ///
+    /// ### Panics
+    /// Panics if the pool has been dropped.
+    #[track_caller]
+    pub fn post<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T) + Send + 'static,
+    {
+        let inner = self.inner.clone();
+        self.inner.porter.post(move || inner.with(f));
+    }
+
+    /// Executes the closure with the value on its thread and returns its result.
+    ///
+    /// When called from the hosting thread, the closure is executed inline.
+    ///
+    /// ### Panics
+    /// Panics if the pool has been dropped.
+    /// Awaiting the returned future panics if the pool is dropped before the
+    /// closure has been executed.
+    #[track_caller]
+    pub fn call<F, R>(&self, f: F) -> Call<R>
+    where
+        F: FnOnce(&mut T) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let inner = self.inner.clone();
+        self.inner.porter.call(move || inner.with(f))
+    }
+}
+
+impl<T> Clone for Pooled<T>
+where
+    T: 'static,
+{
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<T> fmt::Debug for Pooled<T>
+where
+    T: 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pooled").field("thread_id", &self.thread_id()).finish()
+    }
+}
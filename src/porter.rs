@@ -0,0 +1,1420 @@
+//! Run closures on the thread that owns `!Send` values.
+
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::{HashMap, VecDeque},
+    fmt,
+    future::{poll_fn, Future},
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe, RefUnwindSafe},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Condvar, Mutex, MutexGuard, OnceLock, PoisonError,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread,
+    thread::ThreadId,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "metrics")]
+use crate::LatencyHistogram;
+use crate::{
+    deferred, oneshot,
+    spawn::{self, LocalTask},
+    timer::Sleep,
+    CancelToken, DropContext, JoinHandle, Leaky, Policy, ThreadBound,
+};
+
+type Job = Box<dyn FnOnce(&Local) + Send>;
+
+/// The payload of a panic.
+type Payload = Box<dyn Any + Send>;
+
+type PanicHook = Box<dyn Fn(&(dyn Any + Send))>;
+
+/// A queued job.
+struct Entry {
+    job: Job,
+    lossy: bool,
+    key: Option<u64>,
+    #[cfg(feature = "metrics")]
+    submitted: Instant,
+}
+
+/// How a job is admitted into a bounded queue.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Admission {
+    /// Block the calling thread until there is space.
+    Block,
+    /// Ignore the capacity.
+    Bypass,
+    /// Use a slot reserved by a [`Permit`].
+    Reserved,
+    /// Evict the oldest lossy job if necessary.
+    Lossy,
+    /// Replace a queued job with the same key or block like [`Admission::Block`].
+    Coalesce(u64),
+}
+
+/// Where a call is executed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Dispatch {
+    /// Inline when called from the owner thread, otherwise queued.
+    Inline,
+    /// Always queued.
+    Queued,
+    /// Always queued and executed even if the result is dropped.
+    Detached,
+}
+
+struct State {
+    queue: VecDeque<Entry>,
+    reserved: usize,
+    ready: Vec<u64>,
+    waker: Option<Waker>,
+    space_wakers: Vec<Waker>,
+    closed: bool,
+    shutting_down: bool,
+    high_water_mark: usize,
+}
+
+impl State {
+    fn has_space(&self, capacity: Option<usize>) -> bool {
+        capacity.is_none_or(|capacity| self.queue.len() + self.reserved < capacity)
+    }
+}
+
+struct Shared {
+    thread_id: ThreadId,
+    capacity: Option<usize>,
+    state: Mutex<State>,
+    space: Condvar,
+    panic_hook: ThreadBound<RefCell<Option<PanicHook>>, Leaky>,
+    #[cfg(feature = "metrics")]
+    latency: Mutex<LatencyHistogram>,
+}
+
+// The panic hook is only replaced as a whole on the owner thread, so that a panic cannot expose it half-updated.
+impl RefUnwindSafe for Shared {}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Executes the closure on the owner thread, catching a panic and reporting it to the panic hook.
+    fn catch<R>(&self, f: impl FnOnce() -> R) -> Result<R, Payload> {
+        catch_unwind(AssertUnwindSafe(f)).inspect_err(|payload| {
+            if let Some(hook) = &*self.panic_hook.borrow() {
+                hook(&**payload);
+            }
+        })
+    }
+
+    /// Enqueues a job, handing it back if the porter is closed.
+    ///
+    /// While shutting down, only jobs bypassing the capacity, such as forwarded drops, are accepted.
+    fn push(&self, job: Job, admission: Admission) -> Result<(), Job> {
+        let mut evicted = None;
+        let waker = {
+            let mut state = self.lock();
+            if let Admission::Coalesce(key) = admission {
+                if state.closed || state.shutting_down {
+                    return Err(job);
+                }
+                if let Some(entry) = state.queue.iter_mut().find(|entry| entry.key == Some(key)) {
+                    let replaced = std::mem::replace(&mut entry.job, job);
+                    drop(state);
+                    drop(replaced);
+                    return Ok(());
+                }
+            }
+            if matches!(admission, Admission::Block | Admission::Coalesce(_))
+                && thread::current().id() != self.thread_id
+            {
+                while !state.closed && !state.shutting_down && !state.has_space(self.capacity) {
+                    state = self.space.wait(state).unwrap_or_else(PoisonError::into_inner);
+                }
+            }
+            if admission == Admission::Reserved {
+                state.reserved -= 1;
+            }
+            if state.closed || (state.shutting_down && admission != Admission::Bypass) {
+                return Err(job);
+            }
+            if admission == Admission::Lossy && !state.has_space(self.capacity) {
+                match state.queue.iter().position(|entry| entry.lossy) {
+                    Some(pos) => evicted = state.queue.remove(pos),
+                    None => {
+                        drop(state);
+                        drop(job);
+                        return Ok(());
+                    }
+                }
+            }
+            let key = match admission {
+                Admission::Coalesce(key) => Some(key),
+                _ => None,
+            };
+            state.queue.push_back(Entry {
+                job,
+                lossy: admission == Admission::Lossy,
+                key,
+                #[cfg(feature = "metrics")]
+                submitted: Instant::now(),
+            });
+            state.high_water_mark = state.high_water_mark.max(state.queue.len());
+            state.waker.take()
+        };
+        drop(evicted);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    /// Releases a reserved slot.
+    fn release(&self) {
+        let mut state = self.lock();
+        state.reserved -= 1;
+        self.notify_space(&mut state);
+    }
+
+    /// Notifies producers waiting for space in the queue.
+    fn notify_space(&self, state: &mut State) {
+        if self.capacity.is_some() {
+            self.space.notify_all();
+            state.space_wakers.drain(..).for_each(Waker::wake);
+        }
+    }
+
+    /// Marks a local task as ready to be polled.
+    fn schedule(&self, id: u64) {
+        let waker = {
+            let mut state = self.lock();
+            if state.closed {
+                return;
+            }
+            state.ready.push(id);
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// The porters registered for their threads.
+fn registered() -> MutexGuard<'static, HashMap<ThreadId, PorterHandle>> {
+    static REGISTERED: OnceLock<Mutex<HashMap<ThreadId, PorterHandle>>> = OnceLock::new();
+    REGISTERED.get_or_init(Mutex::default).lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Wakes a local task by scheduling it on its porter.
+struct TaskWaker {
+    id: u64,
+    scheduled: AtomicBool,
+    shared: Arc<Shared>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if !self.scheduled.swap(true, Ordering::AcqRel) {
+            self.shared.schedule(self.id);
+        }
+    }
+}
+
+/// Owner-side state of a porter.
+struct Local {
+    shared: Arc<Shared>,
+    tasks: RefCell<HashMap<u64, (LocalTask, Arc<TaskWaker>)>>,
+    next_id: Cell<u64>,
+}
+
+impl Local {
+    fn spawn(&self, task: LocalTask) {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        let waker = Arc::new(TaskWaker { id, scheduled: AtomicBool::new(true), shared: self.shared.clone() });
+        self.tasks.borrow_mut().insert(id, (task, waker));
+        self.shared.schedule(id);
+    }
+
+    /// Executes up to `max` queued closures and ready tasks, in this order.
+    ///
+    /// Returns the number of items processed.
+    fn process(&self, max: usize) -> usize {
+        let (jobs, ready) = {
+            let mut state = self.shared.lock();
+            let jobs_len = max.min(state.queue.len());
+            let jobs: Vec<_> = state.queue.drain(..jobs_len).collect();
+            let ready_len = (max - jobs_len).min(state.ready.len());
+            let ready: Vec<_> = state.ready.drain(..ready_len).collect();
+            if !jobs.is_empty() {
+                self.shared.notify_space(&mut state);
+            }
+            (jobs, ready)
+        };
+        let processed = jobs.len() + ready.len();
+
+        for entry in jobs {
+            let job = entry.job;
+            let _ = self.catch(|| job(self));
+            #[cfg(feature = "metrics")]
+            self.shared.latency.lock().unwrap_or_else(PoisonError::into_inner).record(entry.submitted.elapsed());
+        }
+
+        for id in ready {
+            let Some((mut task, waker)) = self.tasks.borrow_mut().remove(&id) else { continue };
+            waker.scheduled.store(false, Ordering::Release);
+            let task_waker = Waker::from(waker.clone());
+            if task.as_mut().poll(&mut Context::from_waker(&task_waker)).is_pending() {
+                self.tasks.borrow_mut().insert(id, (task, waker));
+            }
+        }
+
+        processed
+    }
+
+    /// Executes the closure, catching a panic and reporting it to the panic hook.
+    fn catch<R>(&self, f: impl FnOnce() -> R) -> Result<R, Payload> {
+        self.shared.catch(f)
+    }
+
+    /// Whether closures are queued or tasks are ready.
+    fn has_pending(&self) -> bool {
+        let state = self.shared.lock();
+        !state.queue.is_empty() || !state.ready.is_empty()
+    }
+
+    /// Executes queued closures and polls ready tasks once,
+    /// then drops deferred values if idle.
+    ///
+    /// Returns whether more work has arrived in the meantime.
+    fn pump(&self, cx: &mut Context) -> bool {
+        self.process(usize::MAX);
+        if !self.has_pending() {
+            deferred::flush_deferred_all();
+        }
+
+        let mut state = self.shared.lock();
+        if state.queue.is_empty() && state.ready.is_empty() {
+            state.waker = Some(cx.waker().clone());
+            false
+        } else {
+            true
+        }
+    }
+}
+
+/// Progress made by [`Porter::poll_pending`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pumped {
+    /// The number of closures executed and tasks polled.
+    pub processed: usize,
+    /// Whether more closures are queued or tasks are ready.
+    pub more: bool,
+}
+
+/// Executes closures and `!Send` tasks submitted from any thread on the thread that created it.
+///
+/// The porter itself must stay on its thread, where [`run`](Self::run) must be
+/// driven by the local executor, or [`poll_pending`](Self::poll_pending) must be
+/// called regularly.
+/// Work is submitted through a [`PorterHandle`], which is [`Send`] + [`Sync`].
+///
+/// Dropping the porter closes it; closures that have not been executed by then
+/// and unfinished tasks are dropped on the owner thread.
+pub struct Porter {
+    shared: Arc<Shared>,
+    local: Local,
+}
+
+impl Porter {
+    /// Creates a porter for the current thread with an unbounded queue.
+    pub fn new() -> Self {
+        Self::with_capacity(None)
+    }
+
+    /// Creates a porter for the current thread whose queue holds at most
+    /// `capacity` closures.
+    ///
+    /// When the queue is full, submitting closures blocks the calling thread
+    /// until space becomes available.
+    /// Use [`PorterHandle::reserve`] to wait for space asynchronously and
+    /// [`PorterHandle::post_lossy`] to drop old closures instead.
+    /// Closures submitted from the owner thread and forwarded drops
+    /// are never subject to the capacity.
+    ///
+    /// ### Panics
+    /// Panics if `capacity` is zero.
+    pub fn bounded(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be positive");
+        Self::with_capacity(Some(capacity))
+    }
+
+    fn with_capacity(capacity: Option<usize>) -> Self {
+        let state = State {
+            queue: VecDeque::new(),
+            reserved: 0,
+            ready: Vec::new(),
+            waker: None,
+            space_wakers: Vec::new(),
+            closed: false,
+            shutting_down: false,
+            high_water_mark: 0,
+        };
+        let shared = Arc::new(Shared {
+            thread_id: thread::current().id(),
+            capacity,
+            state: Mutex::new(state),
+            space: Condvar::new(),
+            panic_hook: ThreadBound::with_policy(RefCell::new(None)),
+            #[cfg(feature = "metrics")]
+            latency: Mutex::new(LatencyHistogram::new()),
+        });
+        let local = Local { shared: shared.clone(), tasks: RefCell::new(HashMap::new()), next_id: Cell::new(0) };
+        Self { shared, local }
+    }
+
+    /// A handle for submitting closures from any thread.
+    pub fn handle(&self) -> PorterHandle {
+        PorterHandle { shared: self.shared.clone() }
+    }
+
+    /// The id of the thread that executes submitted closures.
+    pub fn thread_id(&self) -> ThreadId {
+        self.shared.thread_id
+    }
+
+    /// Registers this porter as the porter of its thread.
+    ///
+    /// This allows routing work to it knowing only the id of its thread,
+    /// for example by using [`spawn_on_owner`].
+    /// A previously registered porter of the thread is replaced.
+    /// The registration is removed when the porter is dropped.
+    pub fn register(&self) {
+        registered().insert(self.shared.thread_id, self.handle());
+    }
+
+    /// Creates a porter for the current thread with an unbounded queue and
+    /// [registers](Self::register) it.
+    ///
+    /// Thread-bound values owned by the thread then discover it automatically,
+    /// for example by [`ThreadBound::invoke_blocking`] and [`ThreadBound::drop_on_owner`].
+    pub fn install() -> Self {
+        let porter = Self::new();
+        porter.register();
+        porter
+    }
+
+    /// Registers a hook that is called on the owner thread with the payload of a panic
+    /// raised by a submitted closure.
+    ///
+    /// Such panics are caught, so that they do not take down the event loop of the owner
+    /// thread, and delivered as [`CallError::Panicked`] to the caller awaiting the result.
+    /// A previously registered hook is replaced.
+    pub fn on_panic(&self, hook: impl Fn(&(dyn Any + Send)) + 'static) {
+        *self.shared.panic_hook.borrow_mut() = Some(Box::new(hook));
+    }
+
+    /// Spawns a `!Send` task that is driven by this porter.
+    pub fn spawn_local<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: Send + 'static,
+    {
+        let (task, handle) = spawn::task(future);
+        self.local.spawn(task);
+        handle
+    }
+
+    /// Executes at most `max_items` submitted closures and polls of ready tasks.
+    ///
+    /// This allows driving the porter manually, for example from a game loop or
+    /// an idle callback, on threads that do not run an executor.
+    /// A batch of closures counts as one item.
+    /// If no more work is pending afterwards, [deferred drops](ThreadBound::defer_drop)
+    /// of the thread are flushed.
+    pub fn poll_pending(&self, max_items: usize) -> Pumped {
+        let processed = self.local.process(max_items);
+        if !self.local.has_pending() {
+            deferred::flush_deferred_all();
+        }
+        Pumped { processed, more: self.local.has_pending() }
+    }
+
+    /// Executes submitted closures and polls ready tasks until no more work is pending.
+    ///
+    /// Returns the number of items processed.
+    /// This does not wait for tasks that are not ready.
+    /// [Deferred drops](ThreadBound::defer_drop) of the thread are flushed when idle.
+    pub fn run_until_idle(&self) -> usize {
+        let mut processed = 0;
+        loop {
+            while self.local.has_pending() {
+                processed += self.local.process(usize::MAX);
+            }
+            deferred::flush_deferred_all();
+            if !self.local.has_pending() {
+                return processed;
+            }
+        }
+    }
+
+    /// Shuts the porter down gracefully.
+    ///
+    /// From now on, submitting closures fails with [`CallError::Closed`] or panics,
+    /// depending on the method, and [`PorterHandle::is_closed`] returns `true`.
+    /// Forwarded drops are still accepted.
+    /// Closures that have already been queued are executed or dropped according to `mode`,
+    /// so that their callers are not left waiting.
+    ///
+    /// The returned future must be driven on the owner thread instead of [`run`](Self::run)
+    /// and completes when the porter is quiescent, i.e. no closures are queued,
+    /// no spawned tasks are left and [deferred drops](ThreadBound::defer_drop) have been flushed.
+    /// With [`Shutdown::Drain`], this waits for all spawned tasks to complete.
+    pub async fn shutdown(&self, mode: Shutdown) {
+        let queue = {
+            let mut state = self.shared.lock();
+            state.shutting_down = true;
+            self.shared.notify_space(&mut state);
+            match mode {
+                Shutdown::Drain => VecDeque::new(),
+                Shutdown::Cancel => {
+                    state.ready.clear();
+                    std::mem::take(&mut state.queue)
+                }
+            }
+        };
+        drop(queue);
+        if mode == Shutdown::Cancel {
+            self.local.tasks.take();
+        }
+
+        poll_fn(|cx| {
+            if self.local.pump(cx) {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            if self.local.tasks.borrow().is_empty() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    /// Executes submitted closures and drives spawned tasks.
+    ///
+    /// This never completes and must be spawned on the local executor of the owner thread.
+    /// It yields back to the executor after each round of work.
+    pub async fn run(&self) {
+        poll_fn(|cx| {
+            if self.local.pump(cx) {
+                cx.waker().wake_by_ref();
+            }
+            Poll::<()>::Pending
+        })
+        .await
+    }
+}
+
+/// How [`Porter::shutdown`] handles queued work.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Shutdown {
+    /// Execute queued closures and wait for spawned tasks to complete.
+    #[default]
+    Drain,
+    /// Drop queued closures and spawned tasks.
+    Cancel,
+}
+
+impl Default for Porter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Porter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Porter").field("thread_id", &self.shared.thread_id).finish()
+    }
+}
+
+impl Drop for Porter {
+    fn drop(&mut self) {
+        {
+            let mut registered = registered();
+            if registered
+                .get(&self.shared.thread_id)
+                .is_some_and(|handle| Arc::ptr_eq(&handle.shared, &self.shared))
+            {
+                registered.remove(&self.shared.thread_id);
+            }
+        }
+
+        let queue = {
+            let mut state = self.shared.lock();
+            state.closed = true;
+            state.waker = None;
+            state.ready.clear();
+            self.shared.notify_space(&mut state);
+            std::mem::take(&mut state.queue)
+        };
+        drop(queue);
+        self.local.tasks.take();
+        // Handles may keep the shared state alive on other threads, where the hook cannot be dropped.
+        self.shared.panic_hook.take();
+    }
+}
+
+/// Submits closures for execution on the thread of a [`Porter`].
+///
+/// The handle can be freely sent and shared between threads.
+#[derive(Clone)]
+pub struct PorterHandle {
+    shared: Arc<Shared>,
+}
+
+impl PorterHandle {
+    /// The porter [registered](Porter::register) for the thread.
+    pub fn for_thread(thread_id: ThreadId) -> Option<Self> {
+        registered().get(&thread_id).cloned()
+    }
+
+    /// The id of the thread that executes submitted closures.
+    pub fn thread_id(&self) -> ThreadId {
+        self.shared.thread_id
+    }
+
+    /// Whether the porter has been dropped or is [shutting down](Porter::shutdown).
+    pub fn is_closed(&self) -> bool {
+        let state = self.shared.lock();
+        state.closed || state.shutting_down
+    }
+
+    /// Whether the current thread is the owner thread.
+    pub fn is_owner(&self) -> bool {
+        thread::current().id() == self.shared.thread_id
+    }
+
+    /// The maximum number of queued closures, if the porter is bounded.
+    pub fn capacity(&self) -> Option<usize> {
+        self.shared.capacity
+    }
+
+    /// The number of queued closures.
+    ///
+    /// A batch of closures counts as one.
+    pub fn queue_len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// The highest number of closures that have been queued at once.
+    pub fn high_water_mark(&self) -> usize {
+        self.shared.lock().high_water_mark
+    }
+
+    /// Resets the [high-water mark](Self::high_water_mark) to the current number of queued closures.
+    pub fn reset_high_water_mark(&self) {
+        let mut state = self.shared.lock();
+        state.high_water_mark = state.queue.len();
+    }
+
+    /// A snapshot of the histogram of latencies from submitting closures
+    /// until they completed executing.
+    ///
+    /// Closures executed inline, because they were called from the owner thread,
+    /// are not recorded.
+    #[cfg(feature = "metrics")]
+    pub fn latency(&self) -> LatencyHistogram {
+        self.shared.latency.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Polls whether the queue currently has space for another closure.
+    ///
+    /// This is advisory, since other producers may fill the queue in the meantime;
+    /// use [`reserve`](Self::reserve) to obtain a guaranteed slot.
+    /// Returns ready if the porter has been dropped.
+    pub fn poll_ready(&self, cx: &mut Context) -> Poll<()> {
+        let mut state = self.shared.lock();
+        if state.closed || state.has_space(self.shared.capacity) {
+            Poll::Ready(())
+        } else {
+            state.space_wakers.push(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    /// Waits until a slot in the queue is available and reserves it.
+    ///
+    /// On the owner thread a slot is granted immediately.
+    ///
+    /// ### Panics
+    /// Awaiting panics if the porter has been dropped.
+    pub fn reserve(&self) -> Reserve {
+        Reserve { handle: Some(self.clone()) }
+    }
+
+    /// Submits the closure for execution on the owner thread without waiting for it,
+    /// dropping the oldest queued lossy closure if the queue is full.
+    ///
+    /// This never blocks and is intended for fire-and-forget work like telemetry.
+    /// If the queue is full and holds no lossy closures, this closure is dropped.
+    /// Dropped closures are dropped on the calling thread.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    #[track_caller]
+    pub fn post_lossy<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if self.shared.push(Box::new(move |_| f()), Admission::Lossy).is_err() {
+            self.closed();
+        }
+    }
+
+    /// Submits the closure for execution on the owner thread without waiting for it.
+    ///
+    /// The closure is always queued, even when called from the owner thread,
+    /// so this can be used to defer work until the current call stack has unwound.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    #[track_caller]
+    pub fn post<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.post_with(f, Admission::Block)
+    }
+
+    /// Submits the closure for execution on the owner thread without waiting for it.
+    ///
+    /// Like [`post`](Self::post), but returns [`CallError::Closed`] instead of panicking
+    /// if the porter has been dropped.
+    pub fn try_post<F>(&self, f: F) -> Result<(), CallError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.shared.push(Box::new(move |_| f()), Admission::Block).map_err(|_| CallError::Closed)
+    }
+
+    /// Submits the closure for execution on the owner thread without waiting for it,
+    /// replacing a still queued closure that was submitted with the same key.
+    ///
+    /// The replacement takes the queue position of the replaced closure,
+    /// which is dropped on the calling thread.
+    /// This is useful for work where only the latest request matters,
+    /// like redrawing after a resize.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    #[track_caller]
+    pub fn post_coalesced<F>(&self, key: u64, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.post_with(f, Admission::Coalesce(key))
+    }
+
+    /// Starts a batch of closures that are submitted together.
+    pub fn batch(&self) -> Batch {
+        Batch { handle: self.clone(), jobs: Vec::new() }
+    }
+
+    #[track_caller]
+    fn post_with<F>(&self, f: F, admission: Admission)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if self.shared.push(Box::new(move |_| f()), admission).is_err() {
+            self.closed();
+        }
+    }
+
+    #[track_caller]
+    fn closed(&self) -> ! {
+        panic!("porter of thread {:?} is closed", self.shared.thread_id)
+    }
+
+    /// Executes the closure on the owner thread and returns its result.
+    ///
+    /// When called from the owner thread, the closure is executed inline before
+    /// this function returns and the returned future is already resolved.
+    /// It thus runs ahead of closures that are still queued and may be executed
+    /// re-entrantly from within another closure or task of the porter.
+    ///
+    /// Dropping the returned future before the closure has started executing
+    /// cancels the call.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    /// Awaiting the returned future panics if the porter is dropped before
+    /// the closure has been executed.
+    /// A panic raised by the closure is caught on the owner thread and
+    /// propagated to the thread awaiting the returned future.
+    #[track_caller]
+    pub fn call<F, R>(&self, f: F) -> Call<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        match self.submit(f, None, None, Admission::Block, Dispatch::Inline) {
+            Some(inner) => Call { inner },
+            None => self.closed(),
+        }
+    }
+
+    /// Executes the closure on the owner thread and returns its result.
+    ///
+    /// Like [`call`](Self::call), but the closure is always queued, even when called
+    /// from the owner thread, like [`post`](Self::post).
+    /// This avoids executing the closure re-entrantly from within another closure or task of the porter.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    /// Awaiting the returned future panics if the porter is dropped before
+    /// the closure has been executed.
+    #[track_caller]
+    pub fn call_queued<F, R>(&self, f: F) -> Call<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        match self.submit(f, None, None, Admission::Block, Dispatch::Queued) {
+            Some(inner) => Call { inner },
+            None => self.closed(),
+        }
+    }
+
+    /// Queues the closure for execution on the owner thread, even when called from it,
+    /// and returns its result.
+    ///
+    /// Unlike [`call_queued`](Self::call_queued), dropping the returned future does not
+    /// cancel the call.
+    #[track_caller]
+    pub(crate) fn call_detached<F, R>(&self, f: F) -> Call<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        match self.submit(f, None, None, Admission::Block, Dispatch::Detached) {
+            Some(inner) => Call { inner },
+            None => self.closed(),
+        }
+    }
+
+    /// Executes the closure on the owner thread and returns its result.
+    ///
+    /// Like [`call`](Self::call), but the returned future resolves to [`CallError::Closed`]
+    /// instead of panicking if the porter is dropped before the closure has been executed.
+    pub fn try_call<F, R>(&self, f: F) -> TryCall<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        self.submit(f, None, None, Admission::Block, Dispatch::Inline).unwrap_or_else(|| TryCall {
+            rx: oneshot::channel().1,
+            thread_id: self.shared.thread_id,
+            sleep: None,
+            cancel: None,
+        })
+    }
+
+    /// Executes the closure on the owner thread and returns its result,
+    /// unless the timeout elapses first.
+    ///
+    /// If the timeout elapses before the closure has started executing,
+    /// it is skipped, even if the returned future is kept.
+    /// A closure that has already started executing when the timeout elapses
+    /// runs to completion, but its result is discarded.
+    /// Timing requires thread support, since deadlines are tracked by a background thread.
+    ///
+    /// See [`call`](Self::call) for more details.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    #[track_caller]
+    pub fn call_with_timeout<F, R>(&self, timeout: Duration, f: F) -> TryCall<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        match self.submit(f, Some(Instant::now() + timeout), None, Admission::Block, Dispatch::Inline) {
+            Some(call) => call,
+            None => self.closed(),
+        }
+    }
+
+    /// Executes the closure on the owner thread and returns its result,
+    /// unless the token is cancelled first.
+    ///
+    /// See [`call`](Self::call) and [`CancelToken`] for more details.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    #[track_caller]
+    pub fn call_cancellable<F, R>(&self, token: &CancelToken, f: F) -> TryCall<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        match self.submit(f, None, Some(token.clone()), Admission::Block, Dispatch::Inline) {
+            Some(call) => call,
+            None => self.closed(),
+        }
+    }
+
+    /// Executes the closure on the owner thread, blocking the calling thread until it has completed.
+    ///
+    /// This is intended for synchronous callers, such as FFI callbacks.
+    /// Since the calling thread waits, the closure may borrow from its environment.
+    ///
+    /// When called from the owner thread, the closure is executed inline,
+    /// since waiting for the porter would deadlock.
+    /// Otherwise, the owner thread must keep driving the porter while the caller waits.
+    ///
+    /// Returns [`CallError::Closed`] if the porter is dropped before the closure has completed
+    /// and [`CallError::Panicked`] if it panics.
+    pub fn call_blocking<F, R>(&self, f: F) -> Result<R, CallError>
+    where
+        F: FnOnce() -> R + Send,
+        R: Send,
+    {
+        if self.is_owner() {
+            return self.shared.catch(f).map_err(CallError::Panicked);
+        }
+
+        let (tx, rx) = mpsc::sync_channel(1);
+        let job = BlockingJob { f: Some(f), tx };
+        let job: Box<dyn FnOnce(&Local) + Send + '_> = Box::new(move |local| job.run(local));
+        // SAFETY: the job signals its completion or drop only after it has dropped the closure,
+        // and this waits for that signal, so that borrows held by the closure do not outlive this call.
+        let job: Job = unsafe { std::mem::transmute(job) };
+        if self.shared.push(job, Admission::Block).is_err() {
+            return Err(CallError::Closed);
+        }
+        match rx.recv() {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(payload)) => Err(CallError::Panicked(payload)),
+            Err(_) => Err(CallError::Closed),
+        }
+    }
+
+    /// Submits the closure, returning `None` if the porter has been dropped.
+    fn submit<F, R>(
+        &self, f: F, deadline: Option<Instant>, token: Option<CancelToken>, admission: Admission,
+        dispatch: Dispatch,
+    ) -> Option<TryCall<R>>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let thread_id = self.shared.thread_id;
+        let sleep = deadline.map(Sleep::until);
+        let cancel = token.map(|token| (token, None));
+
+        if dispatch == Dispatch::Inline && self.is_owner() {
+            let rx = match &cancel {
+                Some((token, _)) if token.is_cancelled() => oneshot::channel().1,
+                _ => oneshot::Receiver::ready(self.shared.catch(f)),
+            };
+            return Some(TryCall { rx, thread_id, sleep, cancel });
+        }
+
+        let (tx, rx) = oneshot::channel();
+        let job_token = cancel.as_ref().map(|(token, _)| token.clone());
+        let job: Job = Box::new(move |local| {
+            let canceled = dispatch != Dispatch::Detached && tx.is_canceled();
+            let expired = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+            if !canceled && !expired && !job_token.is_some_and(|token| token.is_cancelled()) {
+                tx.send(local.catch(f));
+            }
+        });
+        self.shared.push(job, admission).ok()?;
+        Some(TryCall { rx, thread_id, sleep, cancel })
+    }
+
+    /// Constructs a `!Send` future on the owner thread using the [`Send`] factory
+    /// and drives it there.
+    ///
+    /// The returned handle yields the output of the future.
+    /// The factory is always queued, even when called from the owner thread.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    #[track_caller]
+    pub fn spawn<F, Fut>(&self, factory: F) -> JoinHandle<Fut::Output>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future + 'static,
+        Fut::Output: Send + 'static,
+    {
+        match self.try_spawn(factory) {
+            Ok(handle) => handle,
+            Err(_) => self.closed(),
+        }
+    }
+
+    /// Constructs a `!Send` future on the owner thread using the [`Send`] factory
+    /// and drives it there.
+    ///
+    /// Like [`spawn`](Self::spawn), but returns [`CallError::Closed`] instead of panicking
+    /// if the porter has been dropped.
+    pub fn try_spawn<F, Fut>(&self, factory: F) -> Result<JoinHandle<Fut::Output>, CallError>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future + 'static,
+        Fut::Output: Send + 'static,
+    {
+        let (tx, handle) = spawn::join_handle();
+        let job: Job = Box::new(move |local| local.spawn(spawn::task_with(tx, factory())));
+        self.shared.push(job, Admission::Block).map_err(|_| CallError::Closed)?;
+        Ok(handle)
+    }
+
+    /// Submits the closure ignoring the capacity of the queue.
+    ///
+    /// Returns `false` and drops the closure if the porter has been dropped.
+    pub(crate) fn post_bypass<F>(&self, f: F) -> bool
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.shared.push(Box::new(move |_| f()), Admission::Bypass).is_ok()
+    }
+
+    /// Drives the bound future on its owner thread.
+    ///
+    /// Returns `None` and leaks the future if the porter has been dropped.
+    pub(crate) fn spawn_bound<F>(&self, future: ThreadBound<F>) -> Option<JoinHandle<F::Output>>
+    where
+        F: Future + 'static,
+        F::Output: Send + 'static,
+    {
+        self.spawn_bypass(move || ThreadBound::into_inner(future))
+    }
+
+    /// Like [`spawn`](Self::spawn), but ignores the capacity of the queue.
+    ///
+    /// Returns `None` and leaks the factory if the porter has been dropped,
+    /// since it may hold thread-bound values.
+    pub(crate) fn spawn_bypass<F, Fut>(&self, factory: F) -> Option<JoinHandle<Fut::Output>>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future + 'static,
+        Fut::Output: Send + 'static,
+    {
+        let (tx, handle) = spawn::join_handle();
+        let job: Job = Box::new(move |local| local.spawn(spawn::task_with(tx, factory())));
+        match self.shared.push(job, Admission::Bypass) {
+            Ok(()) => Some(handle),
+            Err(job) => {
+                // The job may hold values that cannot be dropped on this thread.
+                std::mem::forget(job);
+                None
+            }
+        }
+    }
+
+    /// Drops the value on its owner thread.
+    ///
+    /// The value is leaked if the porter has been dropped.
+    pub(crate) fn drop_bound<T: 'static, P: Policy>(&self, value: ThreadBound<T, P>) {
+        if ThreadBound::is_usable(&value) {
+            drop(value);
+            return;
+        }
+
+        // The job is only dropped without being run if the porter is dropped.
+        let mut value = value;
+        ThreadBound::set_drop_context(&mut value, DropContext::Orphaned);
+        let job: Job = Box::new(move |_| {
+            let mut value = value;
+            ThreadBound::set_drop_context(&mut value, DropContext::Forwarded);
+        });
+        if let Err(job) = self.shared.push(job, Admission::Bypass) {
+            // The job holds the value, which cannot be dropped on this thread.
+            std::mem::forget(job);
+        }
+    }
+}
+
+/// A closure executed by [`PorterHandle::call_blocking`].
+struct BlockingJob<F, R> {
+    f: Option<F>,
+    tx: mpsc::SyncSender<Result<R, Payload>>,
+}
+
+impl<F, R> BlockingJob<F, R>
+where
+    F: FnOnce() -> R,
+{
+    fn run(mut self, local: &Local) {
+        let f = self.f.take().unwrap();
+        let _ = self.tx.send(local.catch(f));
+    }
+}
+
+impl<F, R> Drop for BlockingJob<F, R> {
+    fn drop(&mut self) {
+        // The closure must be dropped before the sender signals the waiting caller.
+        self.f.take();
+    }
+}
+
+/// Constructs a `!Send` future on the owner thread of the bound value using the [`Send`] factory
+/// and drives it there.
+///
+/// The factory is submitted to the porter [registered](Porter::register) for the
+/// owner thread, so that the caller does not need to know which thread that is.
+///
+/// ### Panics
+/// Panics if no porter is registered for the owner thread.
+#[track_caller]
+pub fn spawn_on_owner<T, F, Fut>(bound: &ThreadBound<T>, factory: F) -> JoinHandle<Fut::Output>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future + 'static,
+    Fut::Output: Send + 'static,
+{
+    let thread_id = ThreadBound::thread_id(bound);
+    match PorterHandle::for_thread(thread_id) {
+        Some(porter) => porter.spawn(factory),
+        None => panic!("no porter is registered for thread {thread_id:?}"),
+    }
+}
+
+/// Constructs a `!Send` future on the owner thread of the bound value using the [`Send`] factory
+/// and drives it there.
+///
+/// Like [`spawn_on_owner`], but returns [`CallError::NoPorter`] instead of panicking if
+/// no porter is registered for the owner thread and [`CallError::Closed`] if it has been dropped.
+pub fn try_spawn_on_owner<T, F, Fut>(
+    bound: &ThreadBound<T>, factory: F,
+) -> Result<JoinHandle<Fut::Output>, CallError>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future + 'static,
+    Fut::Output: Send + 'static,
+{
+    PorterHandle::for_thread(ThreadBound::thread_id(bound)).ok_or(CallError::NoPorter)?.try_spawn(factory)
+}
+
+impl fmt::Debug for PorterHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PorterHandle").field("thread_id", &self.shared.thread_id).finish()
+    }
+}
+
+/// Closures that are submitted together, created by [`PorterHandle::batch`].
+///
+/// The batch occupies a single slot in the queue and its closures are
+/// executed in order, so that the owner thread is woken only once.
+#[must_use = "the batch must be submitted"]
+pub struct Batch {
+    handle: PorterHandle,
+    jobs: Vec<Job>,
+}
+
+impl Batch {
+    /// Number of closures in the batch.
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Whether the batch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Adds a closure to the batch whose result is not needed.
+    pub fn post<F>(&mut self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.jobs.push(Box::new(move |_| f()));
+    }
+
+    /// Adds a closure to the batch and returns its result once the batch has
+    /// been executed.
+    ///
+    /// Awaiting the returned future panics if the batch is dropped without being submitted.
+    pub fn call<F, R>(&mut self, f: F) -> Call<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        self.jobs.push(Box::new(move |local| {
+            if !tx.is_canceled() {
+                tx.send(local.catch(f));
+            }
+        }));
+        Call { inner: TryCall { rx, thread_id: self.handle.thread_id(), sleep: None, cancel: None } }
+    }
+
+    /// Submits all closures of the batch for execution on the owner thread.
+    ///
+    /// The batch is always queued, even when submitted from the owner thread.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    #[track_caller]
+    pub fn submit(self) {
+        let Self { handle, jobs } = self;
+        if jobs.is_empty() {
+            return;
+        }
+
+        let job: Job = Box::new(move |local| {
+            for job in jobs {
+                let _ = local.catch(|| job(local));
+            }
+        });
+        if handle.shared.push(job, Admission::Block).is_err() {
+            handle.closed();
+        }
+    }
+}
+
+impl fmt::Debug for Batch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Batch")
+            .field("thread_id", &self.handle.thread_id())
+            .field("len", &self.jobs.len())
+            .finish()
+    }
+}
+
+/// Future returned by [`PorterHandle::reserve`].
+#[must_use = "futures do nothing unless polled"]
+pub struct Reserve {
+    handle: Option<PorterHandle>,
+}
+
+impl fmt::Debug for Reserve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Reserve").finish_non_exhaustive()
+    }
+}
+
+impl Future for Reserve {
+    type Output = Permit;
+
+    #[track_caller]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Permit> {
+        let handle = self.handle.as_ref().expect("Reserve polled after completion");
+        let shared = &handle.shared;
+
+        let mut state = shared.lock();
+        if state.closed {
+            panic!("porter of thread {:?} is closed", shared.thread_id);
+        }
+        if !state.has_space(shared.capacity) && !handle.is_owner() {
+            state.space_wakers.push(cx.waker().clone());
+            return Poll::Pending;
+        }
+        state.reserved += 1;
+        drop(state);
+
+        Poll::Ready(Permit { handle: self.handle.take() })
+    }
+}
+
+/// A reserved slot in the queue of a [`Porter`].
+///
+/// Dropping the permit without using it releases the slot.
+#[must_use = "dropping the permit releases the reserved slot"]
+pub struct Permit {
+    handle: Option<PorterHandle>,
+}
+
+impl Permit {
+    fn take(mut self) -> PorterHandle {
+        self.handle.take().unwrap()
+    }
+
+    /// Submits the closure into the reserved slot without waiting for it.
+    ///
+    /// See [`PorterHandle::post`] for details.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    #[track_caller]
+    pub fn post<F>(self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.take().post_with(f, Admission::Reserved)
+    }
+
+    /// Executes the closure using the reserved slot and returns its result.
+    ///
+    /// See [`PorterHandle::call`] for details.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    #[track_caller]
+    pub fn call<F, R>(self, f: F) -> Call<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let handle = self.take();
+        if handle.is_owner() {
+            handle.shared.release();
+        }
+        match handle.submit(f, None, None, Admission::Reserved, Dispatch::Inline) {
+            Some(inner) => Call { inner },
+            None => handle.closed(),
+        }
+    }
+}
+
+impl fmt::Debug for Permit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Permit").finish_non_exhaustive()
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.shared.release();
+        }
+    }
+}
+
+/// Error of a remote call.
+#[derive(Debug)]
+pub enum CallError {
+    /// No porter is registered for the owner thread.
+    NoPorter,
+    /// The porter was dropped before the closure was executed.
+    Closed,
+    /// The timeout elapsed before the closure completed.
+    TimedOut,
+    /// The call was cancelled before the closure completed.
+    Cancelled,
+    /// The closure panicked with the contained payload.
+    Panicked(Payload),
+}
+
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoPorter => write!(f, "no porter is registered for the owner thread"),
+            Self::Closed => write!(f, "porter was closed before executing the call"),
+            Self::TimedOut => write!(f, "call timed out"),
+            Self::Cancelled => write!(f, "call was cancelled"),
+            Self::Panicked(_) => write!(f, "call panicked"),
+        }
+    }
+}
+
+impl std::error::Error for CallError {}
+
+/// The result of a closure executed on the owner thread of a [`Porter`].
+///
+/// Dropping it before the closure has started executing cancels the call.
+#[must_use = "futures do nothing unless polled"]
+pub struct Call<R> {
+    inner: TryCall<R>,
+}
+
+impl<R> fmt::Debug for Call<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Call").field("thread_id", &self.inner.thread_id).finish()
+    }
+}
+
+impl<R> Future for Call<R> {
+    type Output = R;
+
+    #[track_caller]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<R> {
+        match Pin::new(&mut self.inner).poll(cx) {
+            Poll::Ready(Ok(value)) => Poll::Ready(value),
+            Poll::Ready(Err(CallError::Panicked(payload))) => resume_unwind(payload),
+            Poll::Ready(Err(err)) => panic!("call on thread {:?} failed: {err}", self.inner.thread_id),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// The result of a closure executed on the owner thread of a [`Porter`]
+/// or the reason why it is unavailable.
+///
+/// Dropping it before the closure has started executing cancels the call.
+#[must_use = "futures do nothing unless polled"]
+pub struct TryCall<R> {
+    rx: oneshot::Receiver<Result<R, Payload>>,
+    thread_id: ThreadId,
+    sleep: Option<Sleep>,
+    cancel: Option<(CancelToken, Option<u64>)>,
+}
+
+impl<R> fmt::Debug for TryCall<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TryCall").field("thread_id", &self.thread_id).finish()
+    }
+}
+
+impl<R> Future for TryCall<R> {
+    type Output = Result<R, CallError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        if let Some((token, id)) = &mut this.cancel {
+            token.register(id, cx.waker());
+        }
+
+        match Pin::new(&mut this.rx).poll(cx) {
+            Poll::Ready(Ok(Ok(value))) => return Poll::Ready(Ok(value)),
+            Poll::Ready(Ok(Err(payload))) => return Poll::Ready(Err(CallError::Panicked(payload))),
+            Poll::Ready(Err(oneshot::Closed)) => {
+                if this.cancel.as_ref().is_some_and(|(token, _)| token.is_cancelled()) {
+                    return Poll::Ready(Err(CallError::Cancelled));
+                }
+                // The closure is skipped once the deadline has passed.
+                if this.sleep.as_ref().is_some_and(Sleep::is_elapsed) {
+                    return Poll::Ready(Err(CallError::TimedOut));
+                }
+                return Poll::Ready(Err(CallError::Closed));
+            }
+            Poll::Pending => (),
+        }
+
+        if this.cancel.as_ref().is_some_and(|(token, _)| token.is_cancelled()) {
+            return Poll::Ready(Err(CallError::Cancelled));
+        }
+
+        if let Some(sleep) = &mut this.sleep {
+            if Pin::new(sleep).poll(cx).is_ready() {
+                return Poll::Ready(Err(CallError::TimedOut));
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<R> Drop for TryCall<R> {
+    fn drop(&mut self) {
+        if let Some((token, Some(id))) = &self.cancel {
+            token.unregister(*id);
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! Canonical [`Send`] representations of `!Send` values.
+
+/// A type that declares its canonical [`Send`] representation,
+/// for example the bytes of a blob or the selector of a DOM element.
+///
+/// Used by [`ThreadBound::project`](crate::ThreadBound::project) to copy the
+/// data out of a thread-bound value, so that another thread can work on it,
+/// and by [`ThreadBound::with_cached_projection`](crate::ThreadBound::with_cached_projection)
+/// to keep a copy that can be read from any thread.
+/// For ad-hoc conversions pass a closure to [`ThreadBound::to_send`](crate::ThreadBound::to_send).
+pub trait SendProjection {
+    /// The [`Send`] representation.
+    type Out: Send;
+
+    /// Converts the value into its [`Send`] representation.
+    fn project(&self) -> Self::Out;
+}
@@ -0,0 +1,73 @@
+//! Thread-bound values that can be recreated on other threads.
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use crate::ThreadBound;
+
+type Recipe<T> = Arc<dyn Fn() -> T + Send + Sync>;
+
+/// A thread-bound value together with a [`Send`] recipe to construct an equivalent value.
+///
+/// This is useful for values that can be recreated from [`Send`] data, for example
+/// a web socket from its URL or a DOM element from its id.
+/// Instead of accessing the original value, [`rebuild_here`](Self::rebuild_here)
+/// constructs a fresh value bound to the calling thread.
+///
+/// The bound value is accessible through [`Deref`] to [`ThreadBound`].
+pub struct RebuildableBound<T> {
+    bound: ThreadBound<T>,
+    recipe: Recipe<T>,
+}
+
+impl<T> RebuildableBound<T> {
+    /// Constructs the value using `recipe` and binds it to the current thread.
+    pub fn new(recipe: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        let recipe: Recipe<T> = Arc::new(recipe);
+        Self { bound: ThreadBound::new(recipe()), recipe }
+    }
+
+    /// Binds the value to the current thread and attaches `recipe` to construct
+    /// equivalent values.
+    pub fn with_value(value: T, recipe: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        Self { bound: ThreadBound::new(value), recipe: Arc::new(recipe) }
+    }
+
+    /// Constructs a fresh value bound to the current thread using the recipe.
+    ///
+    /// The original value is not accessed and the returned value carries the same recipe.
+    pub fn rebuild_here(this: &Self) -> Self {
+        Self { bound: ThreadBound::new((this.recipe)()), recipe: this.recipe.clone() }
+    }
+
+    /// Takes the bound value out, discarding the recipe.
+    pub fn into_bound(this: Self) -> ThreadBound<T> {
+        this.bound
+    }
+}
+
+impl<T> Deref for RebuildableBound<T> {
+    type Target = ThreadBound<T>;
+
+    fn deref(&self) -> &ThreadBound<T> {
+        &self.bound
+    }
+}
+
+impl<T> DerefMut for RebuildableBound<T> {
+    fn deref_mut(&mut self) -> &mut ThreadBound<T> {
+        &mut self.bound
+    }
+}
+
+impl<T> fmt::Debug for RebuildableBound<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RebuildableBound").field("bound", &self.bound).finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,277 @@
+//! Registry of thread-bound values addressed by copyable handles.
+
+use std::{
+    fmt,
+    sync::atomic::{AtomicU32, Ordering},
+    thread::ThreadId,
+};
+
+use crate::ThreadBound;
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+/// A key of a value stored in a [`BoundRegistry`].
+///
+/// Handles are plain integers that are [`Copy`] + [`Send`] + [`Sync`], so they
+/// can be stored in messages, components or passed over FFI boundaries.
+/// They are only resolved by the registry that issued them on its owner thread.
+///
+/// A handle consists of the id of the issuing registry, a slot index and the generation of the slot.
+/// Slots of removed values are reused with an incremented generation, so that
+/// handles outliving their value are detected as [stale](StaleHandle) instead
+/// of resolving to a newer value.
+/// Handles issued by another registry are detected as stale as well.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundHandle(u128);
+
+impl BoundHandle {
+    fn new(registry: u32, index: u32, generation: u32) -> Self {
+        Self((u128::from(registry) << 64) | (u128::from(generation) << 32) | u128::from(index))
+    }
+
+    fn registry(self) -> u32 {
+        (self.0 >> 64) as u32
+    }
+
+    fn index(self) -> u32 {
+        self.0 as u32
+    }
+
+    fn generation(self) -> u32 {
+        (self.0 >> 32) as u32
+    }
+
+    /// The raw integer value of the handle.
+    pub const fn into_raw(self) -> u128 {
+        self.0
+    }
+
+    /// A handle from its raw integer value.
+    pub const fn from_raw(raw: u128) -> Self {
+        Self(raw)
+    }
+}
+
+impl fmt::Debug for BoundHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BoundHandle({}v{}r{})", self.index(), self.generation(), self.registry())
+    }
+}
+
+/// Error returned when resolving a handle whose value has been removed from the registry
+/// or that was issued by another registry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StaleHandle(pub BoundHandle);
+
+impl fmt::Display for StaleHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} does not refer to a value in the registry", self.0)
+    }
+}
+
+impl std::error::Error for StaleHandle {}
+
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+struct Slots<T> {
+    id: u32,
+    entries: Vec<Slot<T>>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl<T> Slots<T> {
+    fn slot(&self, handle: BoundHandle) -> Result<&Slot<T>, StaleHandle> {
+        if handle.registry() != self.id {
+            return Err(StaleHandle(handle));
+        }
+        match self.entries.get(handle.index() as usize) {
+            Some(slot) if slot.generation == handle.generation() && slot.value.is_some() => Ok(slot),
+            _ => Err(StaleHandle(handle)),
+        }
+    }
+
+    fn slot_mut(&mut self, handle: BoundHandle) -> Result<&mut Slot<T>, StaleHandle> {
+        if handle.registry() != self.id {
+            return Err(StaleHandle(handle));
+        }
+        match self.entries.get_mut(handle.index() as usize) {
+            Some(slot) if slot.generation == handle.generation() && slot.value.is_some() => Ok(slot),
+            _ => Err(StaleHandle(handle)),
+        }
+    }
+}
+
+/// Stores values bound to a thread and hands out [`BoundHandle`]s for them, like a slot map.
+///
+/// The registry is [`Send`] + [`Sync`], but values can only be inserted, resolved
+/// and removed on the thread that created the registry.
+///
+/// ### Panics
+/// Panics if the registry is accessed in any way from another thread
+/// (including dropping if its values need drop).
+pub struct BoundRegistry<T> {
+    slots: ThreadBound<Slots<T>>,
+}
+
+impl<T> BoundRegistry<T> {
+    /// Creates an empty registry bound to the current thread.
+    #[track_caller]
+    pub fn new() -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        Self { slots: ThreadBound::new(Slots { id, entries: Vec::new(), free: Vec::new(), len: 0 }) }
+    }
+
+    /// The id of the thread that is allowed to access the registry.
+    pub fn thread_id(&self) -> ThreadId {
+        ThreadBound::thread_id(&self.slots)
+    }
+
+    /// Whether the registry is usable from the current thread.
+    pub fn is_usable(&self) -> bool {
+        ThreadBound::is_usable(&self.slots)
+    }
+
+    /// Stores the value and returns a handle to it.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn insert(&mut self, value: T) -> BoundHandle {
+        let slots = &mut *self.slots;
+        let index = match slots.free.pop() {
+            Some(index) => index,
+            None => {
+                let index = u32::try_from(slots.entries.len()).expect("registry is full");
+                slots.entries.push(Slot { generation: 0, value: None });
+                index
+            }
+        };
+        let slot = &mut slots.entries[index as usize];
+        slot.value = Some(value);
+        slots.len += 1;
+        BoundHandle::new(slots.id, index, slot.generation)
+    }
+
+    /// Removes the value of the handle from the registry and returns it.
+    ///
+    /// The handle and all its copies become stale.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn remove(&mut self, handle: BoundHandle) -> Result<T, StaleHandle> {
+        let slots = &mut *self.slots;
+        let slot = slots.slot_mut(handle)?;
+        let value = slot.value.take().unwrap();
+        slot.generation = slot.generation.wrapping_add(1);
+        slots.free.push(handle.index());
+        slots.len -= 1;
+        Ok(value)
+    }
+
+    /// Whether the handle refers to a value in the registry.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn contains(&self, handle: BoundHandle) -> bool {
+        self.get(handle).is_ok()
+    }
+
+    /// The value of the handle.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn get(&self, handle: BoundHandle) -> Result<&T, StaleHandle> {
+        Ok(self.slots.slot(handle)?.value.as_ref().unwrap())
+    }
+
+    /// The value of the handle mutably.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn get_mut(&mut self, handle: BoundHandle) -> Result<&mut T, StaleHandle> {
+        Ok(self.slots.slot_mut(handle)?.value.as_mut().unwrap())
+    }
+
+    /// Calls `f` with the value of the handle.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn with<R>(&self, handle: BoundHandle, f: impl FnOnce(&T) -> R) -> Result<R, StaleHandle> {
+        self.get(handle).map(f)
+    }
+
+    /// Calls `f` with the value of the handle mutably.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn with_mut<R>(&mut self, handle: BoundHandle, f: impl FnOnce(&mut T) -> R) -> Result<R, StaleHandle> {
+        self.get_mut(handle).map(f)
+    }
+
+    /// Iterates over the handles and values in the registry.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn iter(&self) -> impl Iterator<Item = (BoundHandle, &T)> + '_ {
+        let id = self.slots.id;
+        self.slots.entries.iter().enumerate().filter_map(move |(index, slot)| {
+            let value = slot.value.as_ref()?;
+            Some((BoundHandle::new(id, index as u32, slot.generation), value))
+        })
+    }
+
+    /// Iterates over the handles and values in the registry mutably.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (BoundHandle, &mut T)> + '_ {
+        let slots = &mut *self.slots;
+        let id = slots.id;
+        slots.entries.iter_mut().enumerate().filter_map(move |(index, slot)| {
+            let value = slot.value.as_mut()?;
+            Some((BoundHandle::new(id, index as u32, slot.generation), value))
+        })
+    }
+
+    /// The number of values in the registry.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn len(&self) -> usize {
+        self.slots.len
+    }
+
+    /// Whether the registry contains no values.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Default for BoundRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for BoundRegistry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundRegistry").field("thread_id", &self.thread_id()).finish_non_exhaustive()
+    }
+}
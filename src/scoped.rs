@@ -0,0 +1,75 @@
+//! Thread-bound values tied to the lifetime of a thread scope.
+
+use std::{fmt, marker::PhantomData, ops::Deref, thread::Scope};
+
+use crate::ThreadBound;
+
+/// Binds the value to the current thread for the lifetime of the scope.
+///
+/// This is meant to be called on the thread that invoked [`std::thread::scope`],
+/// so that the bound value can be passed to the scoped threads but cannot
+/// escape the scope and outlive its owner thread.
+pub fn scoped_thread_bound<'scope, 'env, T>(
+    _scope: &'scope Scope<'scope, 'env>, value: T,
+) -> ScopedBound<'scope, T>
+where
+    T: 'scope,
+{
+    ScopedBound { bound: ThreadBound::new(value), _scope: PhantomData }
+}
+
+/// A thread-bound value that cannot outlive the [thread scope](std::thread::scope) it was bound in.
+///
+/// Created by [`scoped_thread_bound`].
+///
+/// The lifetime of the wrapper is tied to the scope, which guarantees at compile time
+/// that the value is dropped before the scope ends and thus while the thread that
+/// created the scope is still running.
+/// The wrapper can be moved to scoped threads, but accessing the value from them
+/// panics like it does for [`ThreadBound`].
+///
+/// The bound value is accessible through [`Deref`] to [`ThreadBound`]
+/// and can be mutated through [`get_mut`](Self::get_mut).
+/// Mutable access to the binding itself is not granted, since the binding could then be
+/// swapped out of the wrapper and escape the scope.
+pub struct ScopedBound<'scope, T> {
+    bound: ThreadBound<T>,
+    _scope: PhantomData<&'scope mut &'scope ()>,
+}
+
+impl<T> ScopedBound<'_, T> {
+    /// Takes the inner value out.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn into_inner(this: Self) -> T {
+        ThreadBound::into_inner(this.bound)
+    }
+
+    /// A mutable reference to the value.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn get_mut(this: &mut Self) -> &mut T {
+        &mut this.bound
+    }
+}
+
+impl<T> Deref for ScopedBound<'_, T> {
+    type Target = ThreadBound<T>;
+
+    fn deref(&self) -> &ThreadBound<T> {
+        &self.bound
+    }
+}
+
+impl<T> fmt::Debug for ScopedBound<'_, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ScopedBound").field("bound", &self.bound).finish()
+    }
+}
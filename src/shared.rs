@@ -0,0 +1,103 @@
+//! [`Sync`] values that must be dropped on their owner thread.
+
+use std::{fmt, ops::Deref, thread::ThreadId};
+
+use crate::{Panicking, Policy, Strict, ThreadBound};
+
+/// A [`Sync`] value that can be accessed by shared reference from any thread,
+/// but must be dropped on the thread that created this.
+///
+/// This suits types that are [`Sync`] but `!Send`, since they can be used
+/// concurrently but must be destroyed on their creating thread.
+/// Shared access is not checked; only taking the value out and dropping it are,
+/// as determined by the policy `P`.
+///
+/// To forward the drop to the owner thread, convert this into a [`ThreadBound`]
+/// using [`into_bound`](Self::into_bound) and use [`ThreadBound::drop_on_owner`].
+///
+/// ### Panics
+/// Panics if dropped on another thread if the value needs drop, unless the policy
+/// leaks the value instead.
+pub struct SharedBound<T, P = Strict>
+where
+    T: Sync,
+    P: Policy,
+{
+    bound: ThreadBound<T, P>,
+}
+
+impl<T> SharedBound<T>
+where
+    T: Sync,
+{
+    /// Binds the value to the current thread.
+    #[track_caller]
+    pub fn new(value: T) -> Self {
+        Self::with_policy(value)
+    }
+}
+
+impl<T, P> SharedBound<T, P>
+where
+    T: Sync,
+    P: Policy,
+{
+    /// Binds the value to the current thread using the policy `P`.
+    #[track_caller]
+    pub fn with_policy(value: T) -> Self {
+        Self { bound: ThreadBound::with_policy(value) }
+    }
+
+    /// The id of the thread that is allowed to drop the value.
+    pub fn thread_id(this: &Self) -> ThreadId {
+        ThreadBound::thread_id(&this.bound)
+    }
+
+    /// Whether the value can be taken out and dropped on the current thread.
+    pub fn is_owner(this: &Self) -> bool {
+        ThreadBound::is_usable(&this.bound)
+    }
+
+    /// Converts this into a thread-bound value, which only allows access on the owner thread.
+    pub fn into_bound(this: Self) -> ThreadBound<T, P> {
+        this.bound
+    }
+}
+
+impl<T, P> SharedBound<T, P>
+where
+    T: Sync,
+    P: Panicking,
+{
+    /// Takes the inner value out.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn into_inner(this: Self) -> T {
+        ThreadBound::into_inner(this.bound)
+    }
+}
+
+impl<T, P> Deref for SharedBound<T, P>
+where
+    T: Sync,
+    P: Policy,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Shared references can be used on any thread, since the value is `Sync`.
+        unsafe { ThreadBound::assume_sync(&self.bound) }
+    }
+}
+
+impl<T, P> fmt::Debug for SharedBound<T, P>
+where
+    T: Sync + fmt::Debug,
+    P: Policy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SharedBound").field("thread_id", &Self::thread_id(self)).field("value", &**self).finish()
+    }
+}
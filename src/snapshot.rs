@@ -0,0 +1,185 @@
+//! Thread-bound values with a [`Send`] snapshot readable from any thread.
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use crate::ThreadBound;
+
+/// Binds the value to the current thread and captures a snapshot of it using `snapshot`.
+pub fn thread_bound_with_snapshot<T, S>(value: T, snapshot: fn(&T) -> S) -> SnapshotBound<T, S> {
+    SnapshotBound::new(value, snapshot)
+}
+
+/// A thread-bound value together with a [`Send`] snapshot of it.
+///
+/// The snapshot is a projection of the value, such as an id, a length or a debug
+/// string, that can be read from any thread, for example for logging.
+/// It is captured when binding and updated by [`refresh`](Self::refresh)
+/// on the owner thread using the same function.
+///
+/// The bound value is accessible through [`Deref`] to [`ThreadBound`].
+/// The [debug representation](fmt::Debug) includes the snapshot and can be
+/// safely used from any thread.
+pub struct SnapshotBound<T, S> {
+    bound: ThreadBound<T>,
+    capture: fn(&T) -> S,
+    snapshot: Mutex<S>,
+}
+
+impl<T, S> SnapshotBound<T, S> {
+    /// Binds the value to the current thread and captures a snapshot of it using `snapshot`.
+    pub fn new(value: T, snapshot: fn(&T) -> S) -> Self {
+        let current = snapshot(&value);
+        Self { bound: ThreadBound::new(value), capture: snapshot, snapshot: Mutex::new(current) }
+    }
+
+    #[track_caller]
+    pub(crate) fn from_bound(bound: ThreadBound<T>, snapshot: fn(&T) -> S) -> Self {
+        let current = snapshot(&bound);
+        Self { bound, capture: snapshot, snapshot: Mutex::new(current) }
+    }
+
+    fn lock(this: &Self) -> MutexGuard<'_, S> {
+        this.snapshot.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// A clone of the last captured snapshot.
+    ///
+    /// This can be called from any thread.
+    pub fn snapshot(this: &Self) -> S
+    where
+        S: Clone,
+    {
+        Self::lock(this).clone()
+    }
+
+    /// Calls `f` with the last captured snapshot.
+    ///
+    /// This can be called from any thread.
+    pub fn with_snapshot<R>(this: &Self, f: impl FnOnce(&S) -> R) -> R {
+        f(&Self::lock(this))
+    }
+
+    /// Captures a new snapshot of the value.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn refresh(this: &Self) {
+        let snapshot = (this.capture)(&this.bound);
+        *Self::lock(this) = snapshot;
+    }
+
+    /// Takes the bound value out, discarding the snapshot.
+    pub fn into_bound(this: Self) -> ThreadBound<T> {
+        this.bound
+    }
+}
+
+impl<T, S> Deref for SnapshotBound<T, S> {
+    type Target = ThreadBound<T>;
+
+    fn deref(&self) -> &ThreadBound<T> {
+        &self.bound
+    }
+}
+
+impl<T, S> DerefMut for SnapshotBound<T, S> {
+    fn deref_mut(&mut self) -> &mut ThreadBound<T> {
+        &mut self.bound
+    }
+}
+
+impl<T, S> fmt::Debug for SnapshotBound<T, S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SnapshotBound")
+            .field("thread_id", &ThreadBound::thread_id(&self.bound))
+            .field("snapshot", &*Self::lock(self))
+            .finish()
+    }
+}
+
+/// A thread-bound value whose [`Display`](fmt::Display) output is cached for use from any thread.
+///
+/// Created by [`ThreadBound::with_cached_display`].
+///
+/// On the owner thread the value is formatted directly.
+/// On other threads formatting yields the text cached by the last call of
+/// [`refresh_display`](Self::refresh_display), marked as stale, instead of panicking.
+///
+/// The bound value is accessible through [`Deref`] to [`ThreadBound`].
+pub struct DisplayBound<T> {
+    inner: SnapshotBound<T, String>,
+}
+
+impl<T> DisplayBound<T>
+where
+    T: fmt::Display,
+{
+    #[track_caller]
+    pub(crate) fn from_bound(bound: ThreadBound<T>) -> Self {
+        Self { inner: SnapshotBound::from_bound(bound, T::to_string) }
+    }
+
+    /// Caches the current display output of the value.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn refresh_display(this: &Self) {
+        SnapshotBound::refresh(&this.inner)
+    }
+
+    /// The cached display output.
+    ///
+    /// This can be called from any thread.
+    pub fn cached_display(this: &Self) -> String {
+        SnapshotBound::snapshot(&this.inner)
+    }
+
+    /// Takes the bound value out, discarding the cached text.
+    pub fn into_bound(this: Self) -> ThreadBound<T> {
+        SnapshotBound::into_bound(this.inner)
+    }
+}
+
+impl<T> Deref for DisplayBound<T> {
+    type Target = ThreadBound<T>;
+
+    fn deref(&self) -> &ThreadBound<T> {
+        &self.inner.bound
+    }
+}
+
+impl<T> DerefMut for DisplayBound<T> {
+    fn deref_mut(&mut self) -> &mut ThreadBound<T> {
+        &mut self.inner.bound
+    }
+}
+
+impl<T> fmt::Display for DisplayBound<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if ThreadBound::is_usable(&self.inner.bound) {
+            return fmt::Display::fmt(&*self.inner.bound, f);
+        }
+        SnapshotBound::with_snapshot(&self.inner, |text| write!(f, "{text} (stale)"))
+    }
+}
+
+impl<T> fmt::Debug for DisplayBound<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DisplayBound")
+            .field("thread_id", &ThreadBound::thread_id(&self.inner.bound))
+            .field("text", &*SnapshotBound::lock(&self.inner))
+            .finish()
+    }
+}
@@ -0,0 +1,187 @@
+//! Spawn `!Send` futures and observe them from any thread.
+
+use std::{
+    any::Any,
+    fmt,
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    task::{Context, Poll, Waker},
+};
+
+use crate::oneshot;
+
+/// A boxed `!Send` task.
+pub(crate) type LocalTask = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Spawns a `!Send` future using the provided local spawner and returns a
+/// [`Send`] + [`Sync`] handle to its output.
+///
+/// The spawner receives the boxed task and must run it on the current thread,
+/// for example `|task| wasm_bindgen_futures::spawn_local(task)` or
+/// `|task| { tokio::task::spawn_local(task); }`.
+///
+/// Panics raised while polling the future are captured and reported
+/// through the handle.
+pub fn spawn_local_bound_with<S, F>(spawn_local: S, future: F) -> JoinHandle<F::Output>
+where
+    S: FnOnce(Pin<Box<dyn Future<Output = ()>>>),
+    F: Future + 'static,
+    F::Output: Send + 'static,
+{
+    let (task, handle) = task(future);
+    spawn_local(task);
+    handle
+}
+
+/// A boxed `!Send` task that reports its output to the returned [`JoinHandle`].
+pub(crate) fn task<F>(future: F) -> (LocalTask, JoinHandle<F::Output>)
+where
+    F: Future + 'static,
+    F::Output: Send + 'static,
+{
+    let (tx, handle) = join_handle();
+    (task_with(tx, future), handle)
+}
+
+/// Abort request for a spawned task.
+#[derive(Default)]
+struct Abort {
+    aborted: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl Abort {
+    fn abort(&self) {
+        self.aborted.store(true, Ordering::Release);
+        let waker = self.waker.lock().unwrap_or_else(PoisonError::into_inner).take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Whether the task has been aborted, registering the waker otherwise.
+    fn poll_aborted(&self, cx: &Context) -> bool {
+        *self.waker.lock().unwrap_or_else(PoisonError::into_inner) = Some(cx.waker().clone());
+        self.aborted.load(Ordering::Acquire)
+    }
+}
+
+/// Receives the output of a task for its [`JoinHandle`].
+pub(crate) struct TaskOutput<T> {
+    tx: oneshot::Sender<Result<T, JoinError>>,
+    abort: Arc<Abort>,
+}
+
+/// A join handle together with the receiver of the task output.
+pub(crate) fn join_handle<T>() -> (TaskOutput<T>, JoinHandle<T>) {
+    let (tx, rx) = oneshot::channel();
+    let abort = Arc::new(Abort::default());
+    (TaskOutput { tx, abort: abort.clone() }, JoinHandle { rx, abort })
+}
+
+/// A boxed `!Send` task that sends its output to `output`.
+pub(crate) fn task_with<F>(output: TaskOutput<F::Output>, future: F) -> LocalTask
+where
+    F: Future + 'static,
+    F::Output: Send + 'static,
+{
+    let TaskOutput { tx, abort } = output;
+    let task = CatchUnwind { future: Box::pin(future), abort };
+    Box::pin(async move { tx.send(task.await) })
+}
+
+struct CatchUnwind<F> {
+    future: Pin<Box<F>>,
+    abort: Arc<Abort>,
+}
+
+impl<F> Future for CatchUnwind<F>
+where
+    F: Future,
+{
+    type Output = Result<F::Output, JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if self.abort.poll_aborted(cx) {
+            return Poll::Ready(Err(JoinError::Cancelled));
+        }
+
+        match catch_unwind(AssertUnwindSafe(|| self.future.as_mut().poll(cx))) {
+            Ok(Poll::Ready(value)) => Poll::Ready(Ok(value)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(JoinError::Panicked(payload))),
+        }
+    }
+}
+
+/// The reason a spawned task did not produce its output.
+pub enum JoinError {
+    /// The task panicked; the panic payload is provided.
+    Panicked(Box<dyn Any + Send>),
+    /// The task was aborted or dropped by its executor before completion.
+    Cancelled,
+}
+
+impl fmt::Debug for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Panicked(_) => write!(f, "Panicked(..)"),
+            Self::Cancelled => write!(f, "Cancelled"),
+        }
+    }
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Panicked(_) => write!(f, "task panicked"),
+            Self::Cancelled => write!(f, "task was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for JoinError {}
+
+/// Handle to the output of a spawned `!Send` task.
+///
+/// The handle is [`Send`] + [`Sync`] and can be stored inside types that must be so.
+/// Dropping the handle detaches the task.
+#[must_use = "futures do nothing unless polled"]
+pub struct JoinHandle<T> {
+    rx: oneshot::Receiver<Result<T, JoinError>>,
+    abort: Arc<Abort>,
+}
+
+impl<T> JoinHandle<T> {
+    /// Requests the task to be aborted.
+    ///
+    /// The task is dropped on its thread the next time it would be polled
+    /// and the handle then yields [`JoinError::Cancelled`], unless the task
+    /// has completed already.
+    pub fn abort(&self) {
+        self.abort.abort();
+    }
+}
+
+impl<T> fmt::Debug for JoinHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinHandle").finish_non_exhaustive()
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        match Pin::new(&mut self.rx).poll(cx) {
+            Poll::Ready(Ok(result)) => Poll::Ready(result),
+            Poll::Ready(Err(oneshot::Closed)) => Poll::Ready(Err(JoinError::Cancelled)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Context values for server-side rendered web frameworks.
+
+use std::fmt;
+
+#[cfg(target_arch = "wasm32")]
+use crate::ThreadBound;
+
+/// A [`Send`] + [`Sync`] cell for values that only exist on the client.
+///
+/// SSR frameworks require context values to be [`Send`] + [`Sync`], even though
+/// client code stores JavaScript objects in them.
+/// On WebAssembly targets (the client) the value is constructed and bound to the
+/// current thread.
+/// On all other targets (the server) the value is never constructed and
+/// the cell holds no data.
+///
+/// ### Panics
+/// On the client, accessing the value from a thread other than the one that
+/// created it panics, like [`ThreadBound`](crate::ThreadBound) does.
+pub struct SsrSafe<T> {
+    #[cfg(target_arch = "wasm32")]
+    value: Option<ThreadBound<T>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    value: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> SsrSafe<T> {
+    /// Whether values are constructed on the current target.
+    pub const IS_CLIENT: bool = cfg!(target_arch = "wasm32");
+
+    /// Constructs the value using `init` on the client only.
+    #[allow(unused_variables)]
+    pub fn new(init: impl FnOnce() -> T) -> Self {
+        #[cfg(target_arch = "wasm32")]
+        return Self { value: Some(ThreadBound::new(init())) };
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self::empty();
+    }
+
+    /// A cell without a value.
+    pub fn empty() -> Self {
+        #[cfg(target_arch = "wasm32")]
+        return Self { value: None };
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self { value: std::marker::PhantomData };
+    }
+
+    /// The value, if it has been constructed on the client.
+    ///
+    /// ### Panics
+    /// Panics if the value is accessed from another thread than the one that created it.
+    #[track_caller]
+    pub fn get(&self) -> Option<&T> {
+        #[cfg(target_arch = "wasm32")]
+        return self.value.as_deref();
+        #[cfg(not(target_arch = "wasm32"))]
+        return None;
+    }
+
+    /// The value mutably, if it has been constructed on the client.
+    ///
+    /// ### Panics
+    /// Panics if the value is accessed from another thread than the one that created it.
+    #[track_caller]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        #[cfg(target_arch = "wasm32")]
+        return self.value.as_deref_mut();
+        #[cfg(not(target_arch = "wasm32"))]
+        return None;
+    }
+
+    /// Calls `f` with the value, if it has been constructed on the client.
+    ///
+    /// ### Panics
+    /// Panics if the value is accessed from another thread than the one that created it.
+    #[track_caller]
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.get().map(f)
+    }
+}
+
+impl<T> Default for SsrSafe<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T> Clone for SsrSafe<T>
+where
+    T: Clone,
+{
+    #[track_caller]
+    fn clone(&self) -> Self {
+        #[cfg(target_arch = "wasm32")]
+        return Self { value: self.value.clone() };
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self::empty();
+    }
+}
+
+impl<T> fmt::Debug for SsrSafe<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut d = f.debug_struct("SsrSafe");
+        #[cfg(target_arch = "wasm32")]
+        if let Some(value) = &self.value {
+            d.field("value", value);
+        }
+        d.finish()
+    }
+}
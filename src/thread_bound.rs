@@ -1,12 +1,20 @@
 //! Bind a value to a thread.
 
+#[cfg(feature = "diagnostics")]
+use crate::{live, trace::AccessTrace, Access, LiveBound};
+use crate::{
+    liveness, BoundGuard, BoundGuardMut, CallError, DispatchedFuture, DisplayBound, JoinError, Leaky, Lenient,
+    LenientFuture, LenientSink, LenientStream, MetaBound, Panicking, Policy, PorterHandle, ScopeTake,
+    SendProjection, SnapshotBound, Strict,
+};
 use futures_core::Stream;
 use futures_sink::Sink;
 use std::{
-    any::type_name,
+    any::{type_name, Any},
     fmt,
     future::Future,
-    mem::{needs_drop, ManuallyDrop},
+    marker::PhantomData,
+    mem::{self, needs_drop, ManuallyDrop},
     ops::{Deref, DerefMut},
     pin::Pin,
     task::{Context, Poll},
@@ -19,6 +27,64 @@ pub fn thread_bound<T>(value: T) -> ThreadBound<T> {
     ThreadBound::new(value)
 }
 
+/// Binds the value to the current thread and pins it on the heap.
+#[track_caller]
+pub fn thread_bound_pin<T>(value: T) -> Pin<Box<ThreadBound<T>>> {
+    ThreadBound::pin(value)
+}
+
+/// Binds the value to the current thread and labels it for diagnostics.
+pub fn thread_bound_named<T>(value: T, label: &'static str) -> ThreadBound<T> {
+    ThreadBound::new_named(value, label)
+}
+
+/// A shared reference bound to the current thread.
+///
+/// This allows lending a reference to a `!Sync` value into an API that requires
+/// [`Send`] + [`Sync`] without transferring ownership.
+/// The referenced value can only be accessed on the thread that created the binding.
+pub type BoundRef<'a, T> = ThreadBound<&'a T>;
+
+/// A mutable reference bound to the current thread.
+///
+/// This allows lending a mutable reference to a `!Send` value into an API that requires
+/// [`Send`] + [`Sync`] without transferring ownership.
+/// The referenced value can only be accessed on the thread that created the binding.
+pub type BoundMut<'a, T> = ThreadBound<&'a mut T>;
+
+/// Binds the shared reference to the current thread.
+pub fn bound_ref<T>(value: &T) -> BoundRef<'_, T>
+where
+    T: ?Sized,
+{
+    ThreadBound::new(value)
+}
+
+/// Binds the mutable reference to the current thread.
+pub fn bound_mut<T>(value: &mut T) -> BoundMut<'_, T>
+where
+    T: ?Sized,
+{
+    ThreadBound::new(value)
+}
+
+/// Error returned when a thread-bound value is accessed from another thread than its owner thread.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WrongThread {
+    /// The thread that is allowed to access the value.
+    pub owner: ThreadId,
+    /// The thread that attempted to access the value.
+    pub current: ThreadId,
+}
+
+impl fmt::Display for WrongThread {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot use value on thread {:?} since it belongs to thread {:?}", self.current, self.owner)
+    }
+}
+
+impl std::error::Error for WrongThread {}
+
 /// Allows access to a value only from the thread that created this,
 /// but always implements [`Send`] and [`Sync`].
 ///
@@ -28,22 +94,219 @@ pub fn thread_bound<T>(value: T) -> ThreadBound<T> {
 ///
 /// The [debug representation](fmt::Debug) can be safely used from any thread.
 ///
+/// On nightly Rust, enabling the `nightly` feature of this crate together with the
+/// `arbitrary_self_types` language feature allows thread-bound values as method receivers,
+/// as in `fn title(self: &ThreadBound<Self>)` or `fn close(self: ThreadBound<Self>)`,
+/// under all policies.
+/// Since the value of a binding must be sized, such trait methods require `where Self: Sized`
+/// and cannot be called on trait objects; bind a `Box<dyn Trait>` instead and call
+/// its `&self` methods through the binding.
+///
+/// An optional label can be attached, which is included in the debug representation
+/// and in panic messages.
+///
 /// ### Panics
 /// Panics if the inner value is accessed in any way from another thread
 /// (including dropping if it needs drop).
-pub struct ThreadBound<T> {
+///
+/// This behavior is determined by the [`Policy`] type parameter, so that it
+/// can be expressed in signatures:
+/// - [`Strict`] (the default) panics as described above,
+/// - [`Leaky`](crate::Leaky) leaks the value if it is dropped on another thread,
+/// - [`Lenient`] never panics; its accessors return a [`Result`]
+///   and the value is leaked if it is dropped on another thread.
+///
+/// If the `debug-checks-only` feature is enabled, these thread checks are omitted in
+/// builds without debug assertions, while accessing a [taken](ThreadBound::is_taken) value still panics.
+/// Accessing the inner value from another thread then is undefined behavior,
+/// so this should only be enabled by the final application after its
+/// thread usage has been verified by debug builds and tests.
+pub struct ThreadBound<T, P = Strict>
+where
+    P: Policy,
+{
     value: ManuallyDrop<T>,
     thread_id: ThreadId,
+    label: Option<&'static str>,
     taken: bool,
+    on_drop: Option<DropHook<T>>,
+    drop_context: DropContext,
+    #[cfg(feature = "diagnostics")]
+    trace: Option<Box<AccessTrace>>,
+    #[cfg(feature = "diagnostics")]
+    live: Option<u64>,
+    _policy: PhantomData<P>,
 }
 
-unsafe impl<T> Send for ThreadBound<T> {}
-unsafe impl<T> Sync for ThreadBound<T> {}
+type DropHook<T> = Box<dyn FnOnce(&mut T, DropContext)>;
+
+/// How a thread-bound value came to be dropped, passed to its [drop hook](ThreadBound::on_drop).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropContext {
+    /// The value was dropped on its owner thread.
+    Local,
+    /// The value was dropped on another thread and its drop was forwarded
+    /// to the owner thread by a [`Porter`](crate::Porter).
+    Forwarded,
+    /// The drop of the value was forwarded to the owner thread, but the
+    /// [`Porter`](crate::Porter) was dropped before handling it.
+    Orphaned,
+}
+
+unsafe impl<T, P> Send for ThreadBound<T, P> where P: Policy {}
+unsafe impl<T, P> Sync for ThreadBound<T, P> where P: Policy {}
 
 impl<T> ThreadBound<T> {
     /// Binds the value to the current thread.
+    #[track_caller]
     pub fn new(value: T) -> Self {
-        Self { thread_id: thread::current().id(), value: ManuallyDrop::new(value), taken: false }
+        Self::bind(value, None)
+    }
+
+    /// Binds the value to the current thread and labels it for diagnostics.
+    #[track_caller]
+    pub fn new_named(value: T, label: &'static str) -> Self {
+        Self::bind(value, Some(label))
+    }
+
+    /// Binds the value to the current thread and pins it on the heap,
+    /// for example to poll a `!Unpin` future.
+    #[track_caller]
+    pub fn pin(value: T) -> Pin<Box<Self>> {
+        Box::pin(Self::new(value))
+    }
+
+    /// Binds the value to the current thread with an optional label.
+    #[track_caller]
+    pub(crate) fn with_label(value: T, label: Option<&'static str>) -> Self {
+        Self::bind(value, label)
+    }
+
+    /// Attaches metadata that is accessible from any thread.
+    pub fn with_meta<M>(this: Self, meta: M) -> MetaBound<T, M> {
+        MetaBound::from_parts(this, meta)
+    }
+}
+
+impl<T, P> ThreadBound<T, P>
+where
+    P: Policy,
+{
+    /// Binds the value to the current thread using the policy `P`.
+    #[track_caller]
+    pub fn with_policy(value: T) -> Self {
+        Self::bind(value, None)
+    }
+
+    /// Binds the value to the current thread using the policy `P` and labels it for diagnostics.
+    #[track_caller]
+    pub fn with_policy_named(value: T, label: &'static str) -> Self {
+        Self::bind(value, Some(label))
+    }
+
+    #[track_caller]
+    fn bind(value: T, label: Option<&'static str>) -> Self {
+        let thread_id = thread::current().id();
+        liveness::acquire(thread_id);
+        #[cfg(feature = "diagnostics")]
+        let location = std::panic::Location::caller();
+        Self {
+            thread_id,
+            value: ManuallyDrop::new(value),
+            label,
+            taken: false,
+            on_drop: None,
+            drop_context: DropContext::Local,
+            #[cfg(feature = "diagnostics")]
+            trace: None,
+            #[cfg(feature = "diagnostics")]
+            live: live::register(|| LiveBound { type_name: type_name::<T>(), label, owner: thread_id, location }),
+            _policy: PhantomData,
+        }
+    }
+
+    /// Pins the boxed binding.
+    ///
+    /// The value is [structurally pinned](Self::as_pin_mut) within the binding.
+    pub fn into_pin(this: Box<Self>) -> Pin<Box<Self>> {
+        Box::into_pin(this)
+    }
+
+    /// Switches to the policy `Q`, keeping the value, its owner thread, label and drop hook.
+    ///
+    /// ### Panics
+    /// Panics if the value [has been taken](Self::is_taken).
+    #[track_caller]
+    pub fn into_policy<Q>(mut this: Self) -> ThreadBound<T, Q>
+    where
+        Q: Policy,
+    {
+        if this.taken {
+            this.already_taken();
+        }
+        this.taken = true;
+        liveness::acquire(this.thread_id);
+        ThreadBound {
+            value: ManuallyDrop::new(unsafe { ManuallyDrop::take(&mut this.value) }),
+            thread_id: this.thread_id,
+            label: this.label,
+            taken: false,
+            on_drop: this.on_drop.take(),
+            drop_context: this.drop_context,
+            #[cfg(feature = "diagnostics")]
+            trace: this.trace.take(),
+            #[cfg(feature = "diagnostics")]
+            live: this.live.take(),
+            _policy: PhantomData,
+        }
+    }
+
+    /// Erases the type of the value, keeping its owner thread, label and drop hook.
+    #[track_caller]
+    pub(crate) fn into_any(mut this: Self) -> ThreadBound<Box<dyn Any>, P>
+    where
+        T: 'static,
+    {
+        if this.taken {
+            this.already_taken();
+        }
+        this.taken = true;
+        let on_drop = this.on_drop.take().map(|hook| -> DropHook<Box<dyn Any>> {
+            Box::new(move |value: &mut Box<dyn Any>, context| hook(value.downcast_mut().unwrap(), context))
+        });
+        liveness::acquire(this.thread_id);
+        ThreadBound {
+            value: ManuallyDrop::new(Box::new(unsafe { ManuallyDrop::take(&mut this.value) })),
+            thread_id: this.thread_id,
+            label: this.label,
+            taken: false,
+            on_drop,
+            drop_context: this.drop_context,
+            #[cfg(feature = "diagnostics")]
+            trace: this.trace.take(),
+            #[cfg(feature = "diagnostics")]
+            live: this.live.take(),
+            _policy: PhantomData,
+        }
+    }
+
+    /// The label used for diagnostics.
+    pub fn label(this: &Self) -> Option<&'static str> {
+        this.label
+    }
+
+    /// Registers a hook that is called on the owner thread right before the value is dropped.
+    ///
+    /// The hook is not called if the value is taken out using [`into_inner`](Self::into_inner)
+    /// and it is not cloned along with the value.
+    pub fn on_drop(mut this: Self, hook: impl FnOnce(&mut T, DropContext) + 'static) -> Self {
+        this.on_drop = Some(Box::new(hook));
+        this
+    }
+
+    /// Sets the context passed to the drop hook.
+    pub(crate) fn set_drop_context(this: &mut Self, context: DropContext) {
+        this.drop_context = context;
     }
 
     /// The id of the thread that is allowed to access the inner value.
@@ -51,38 +314,410 @@ impl<T> ThreadBound<T> {
         this.thread_id
     }
 
-    /// Takes the inner value out.
+    /// Whether the value is usable from the current thread.
+    ///
+    /// This is `false` on all threads while the value is [taken out](Self::scope_take).
+    #[inline]
+    pub fn is_usable(this: &Self) -> bool {
+        !this.taken && thread::current().id() == this.thread_id
+    }
+
+    /// The address of the inner value.
+    ///
+    /// This can be called from any thread, since it does not access the value,
+    /// for example to compare or hash values by identity or for logging.
+    /// The pointer must not be dereferenced on another thread than the owner thread.
+    #[inline]
+    pub fn as_ptr(this: &Self) -> *const T {
+        std::ptr::addr_of!(this.value).cast()
+    }
+
+    /// Whether both refer to the same inner value.
+    ///
+    /// This can be called from any thread.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        std::ptr::eq(Self::as_ptr(this), Self::as_ptr(other))
+    }
+
+    /// Whether the owner thread is still running.
+    ///
+    /// Once the owner thread has exited, the value is permanently unusable and
+    /// is leaked or panics when dropped, depending on the [`Policy`].
+    /// This allows detecting such orphaned values, for example to evict them from caches.
+    ///
+    /// This can be called from any thread.
+    pub fn is_owner_alive(this: &Self) -> bool {
+        !liveness::has_exited(this.thread_id)
+    }
+
+    /// Takes the inner value out on the current thread, if the owner thread has exited.
+    ///
+    /// This rescues values that would otherwise be leaked forever, for example
+    /// after a worker thread crashed.
+    /// The [drop hook](Self::on_drop) is dropped without being called.
+    ///
+    /// ### Safety
+    /// The caller must ensure that the value and the drop hook have no thread-affine
+    /// invariants, i.e. that they can be safely moved to the current thread as if they
+    /// were [`Send`].
+    pub unsafe fn reclaim_if_orphaned(mut this: Self) -> Result<T, Self> {
+        if this.taken || Self::is_owner_alive(&this) {
+            return Err(this);
+        }
+        this.taken = true;
+        Ok(unsafe { ManuallyDrop::take(&mut this.value) })
+    }
+
+    /// Takes the inner value out, if called from the owner thread.
+    pub fn try_into_inner(mut this: Self) -> Result<T, Self> {
+        if !Self::is_usable(&this) {
+            return Err(this);
+        }
+        this.taken = true;
+        Ok(unsafe { ManuallyDrop::take(&mut this.value) })
+    }
+
+    /// Calls `f` with a reference to the value, if called from the owner thread.
+    #[track_caller]
+    pub fn try_inspect<R>(this: &Self, f: impl FnOnce(&T) -> R) -> Result<R, WrongThread> {
+        this.try_check()?;
+        Ok(f(&this.value))
+    }
+
+    /// Calls `f` with a mutable reference to the value, if called from the owner thread.
+    #[track_caller]
+    pub fn try_update<R>(this: &mut Self, f: impl FnOnce(&mut T) -> R) -> Result<R, WrongThread> {
+        this.try_check()?;
+        Ok(f(&mut this.value))
+    }
+
+    /// Clones the value along with its label, if called from the owner thread.
+    ///
+    /// Unlike [`Clone`], this returns `None` instead of panicking when called from another thread.
+    #[track_caller]
+    pub fn try_clone(this: &Self) -> Option<Self>
+    where
+        T: Clone,
+    {
+        this.try_check().ok()?;
+        Some(Self::bind((*this.value).clone(), this.label))
+    }
+
+    /// A reference to the value from any thread, without checking the current thread.
+    ///
+    /// This expresses that the shared API of the value is known to be thread-safe,
+    /// for example an immutable JavaScript string handle in a single-threaded
+    /// WebAssembly build.
+    ///
+    /// ### Safety
+    /// The caller must ensure that the value can be safely accessed through a shared
+    /// reference from the current thread, concurrently with the owner thread,
+    /// i.e. that the shared API of the value behaves as if it was [`Sync`].
     ///
     /// ### Panics
-    /// Panics if this was created by another thread.
+    /// Panics if the value [has been taken](Self::is_taken).
+    #[inline]
     #[track_caller]
-    pub fn into_inner(mut this: Self) -> T {
-        this.check();
+    pub unsafe fn assume_sync(this: &Self) -> &T {
+        if this.taken {
+            this.already_taken();
+        }
+        &this.value
+    }
+
+    /// A reference to the value, if called from the owner thread.
+    #[track_caller]
+    pub(crate) fn try_get(this: &Self) -> Result<&T, WrongThread> {
+        this.try_check()?;
+        Ok(&this.value)
+    }
+
+    /// A mutable reference to the value, if called from the owner thread.
+    #[track_caller]
+    pub(crate) fn try_get_mut(this: &mut Self) -> Result<&mut T, WrongThread> {
+        this.try_check()?;
+        Ok(&mut this.value)
+    }
+
+    /// Starts recording the last `capacity` accesses to the value.
+    ///
+    /// Every checked access records the caller location, the accessing thread and
+    /// the time, and the recorded accesses are included in the panic message
+    /// when the value is used from another thread.
+    #[cfg(feature = "diagnostics")]
+    pub fn trace_accesses(mut this: Self, capacity: usize) -> Self {
+        this.trace = Some(Box::new(AccessTrace::new(capacity)));
+        this
+    }
+
+    /// The recorded accesses to the value, oldest first.
+    ///
+    /// This can be called from any thread.
+    /// It returns an empty list if accesses are not [traced](Self::trace_accesses).
+    #[cfg(feature = "diagnostics")]
+    pub fn accesses(this: &Self) -> Vec<Access> {
+        this.trace.as_ref().map(|trace| trace.accesses()).unwrap_or_default()
+    }
+
+    /// Moves the value out, leaving the binding empty until it is restored.
+    ///
+    /// Must only be called on the owner thread while the value is present.
+    pub(crate) fn take_value(this: &mut Self) -> T {
+        debug_assert!(!this.taken);
         this.taken = true;
         unsafe { ManuallyDrop::take(&mut this.value) }
     }
 
-    /// Whether the value is usable from the current thread.
+    /// Puts the value back into the binding, dropping the present value, if any.
+    ///
+    /// Must only be called on the owner thread.
+    pub(crate) fn restore_value(this: &mut Self, value: T) {
+        if this.taken {
+            this.value = ManuallyDrop::new(value);
+            this.taken = false;
+        } else {
+            *this.value = value;
+        }
+    }
+
+    /// Whether the value has been taken out of the binding.
+    ///
+    /// This is the case while a [`ScopeTake`] guard holds the value, and stays so if
+    /// the guard is leaked or dropped by a panic without restoring it.
+    /// Any further access then panics with an error stating that the value has already
+    /// been taken, on all threads and regardless of the policy.
+    ///
+    /// This can be called from any thread.
+    pub fn is_taken(this: &Self) -> bool {
+        this.taken
+    }
+
+    #[cold]
+    #[track_caller]
+    fn already_taken(&self) -> ! {
+        let label = self.label.map(|label| format!(" ({label})")).unwrap_or_default();
+        panic!("cannot use {}{label} since its value has already been taken", type_name::<T>());
+    }
+
     #[inline]
-    pub fn is_usable(this: &Self) -> bool {
-        thread::current().id() == this.thread_id
+    #[track_caller]
+    fn record_access(&self) {
+        #[cfg(feature = "diagnostics")]
+        if let Some(trace) = &self.trace {
+            trace.record(std::panic::Location::caller());
+        }
+    }
+
+    fn trace_dump(&self) -> String {
+        #[cfg(feature = "diagnostics")]
+        if let Some(trace) = &self.trace {
+            return trace.to_string();
+        }
+        String::new()
     }
 
     #[inline]
     #[track_caller]
-    fn check(&self) {
-        if !Self::is_usable(self) {
+    fn try_check(&self) -> Result<(), WrongThread> {
+        self.record_access();
+        if self.taken {
+            self.already_taken();
+        }
+        match thread::current().id() {
+            current if current == self.thread_id => Ok(()),
+            current => Err(WrongThread { owner: self.thread_id, current }),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    pub(crate) fn check(&self) {
+        // Accessing an empty binding is never sound, so this is checked even if thread checks are omitted.
+        if self.taken {
+            self.already_taken();
+        }
+        if cfg!(all(feature = "debug-checks-only", not(debug_assertions))) {
+            return;
+        }
+
+        self.record_access();
+        if thread::current().id() != self.thread_id {
+            let label = self.label.map(|label| format!(" ({label})")).unwrap_or_default();
             panic!(
-                "cannot use {} on thread {:?} since it belongs to thread {:?}",
+                "cannot use {}{label} on thread {:?} since it belongs to thread {:?}{}",
                 type_name::<T>(),
                 thread::current().id(),
-                self.thread_id
+                self.thread_id,
+                self.trace_dump()
             );
         }
     }
 }
 
-impl<T> Deref for ThreadBound<T> {
+impl<T, P> ThreadBound<T, P>
+where
+    P: Panicking,
+{
+    /// Takes the inner value out.
+    ///
+    /// ### Panics
+    /// Panics if this was created by another thread.
+    #[track_caller]
+    pub fn into_inner(mut this: Self) -> T {
+        this.check();
+        this.taken = true;
+        unsafe { ManuallyDrop::take(&mut this.value) }
+    }
+
+    /// Checks that the current thread is the owner thread once and
+    /// grants shared access to the value.
+    ///
+    /// The guard is `!Send` and thus cannot be held across an `.await` in a
+    /// future that must be [`Send`].
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn guard(this: &Self) -> BoundGuard<'_, T> {
+        this.check();
+        BoundGuard::new(&this.value)
+    }
+
+    /// Checks that the current thread is the owner thread once and
+    /// grants exclusive access to the value.
+    ///
+    /// The guard is `!Send` and thus cannot be held across an `.await` in a
+    /// future that must be [`Send`].
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn guard_mut(this: &mut Self) -> BoundGuardMut<'_, T> {
+        this.check();
+        BoundGuardMut::new(&mut this.value)
+    }
+
+    /// Takes the value out for the duration of a scope, so that it can be passed by value,
+    /// while keeping the binding together with its label, drop hook and other metadata.
+    ///
+    /// The value is moved out by [`ScopeTake::take`] and must be put back, or replaced,
+    /// by [`ScopeTake::restore`] before the returned guard is dropped.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn scope_take(this: &mut Self) -> ScopeTake<'_, T, P> {
+        this.check();
+        ScopeTake::new(this)
+    }
+
+    /// Calls `f` with a reference to the value.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn inspect<R>(this: &Self, f: impl FnOnce(&T) -> R) -> R {
+        this.check();
+        f(&this.value)
+    }
+
+    /// Calls `f` with a mutable reference to the value.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn update<R>(this: &mut Self, f: impl FnOnce(&mut T) -> R) -> R {
+        this.check();
+        f(&mut this.value)
+    }
+
+    /// Converts the value into a [`Send`] representation using `f`, so that
+    /// another thread can work on it.
+    ///
+    /// Types declaring their canonical representation by implementing [`SendProjection`]
+    /// can use [`project`](Self::project) instead.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn to_send<R>(this: &Self, f: impl FnOnce(&T) -> R) -> R
+    where
+        R: Send,
+    {
+        this.check();
+        f(&this.value)
+    }
+
+    /// The canonical [`Send`] representation of the value.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn project(this: &Self) -> T::Out
+    where
+        T: SendProjection,
+    {
+        this.check();
+        this.value.project()
+    }
+
+    /// A detached clone of the inner value.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn cloned(this: &Self) -> T
+    where
+        T: Clone,
+    {
+        this.check();
+        (*this.value).clone()
+    }
+
+    /// Projects a pinned reference to the binding to a pinned reference to the value.
+    ///
+    /// The value is structurally pinned, so that a binding of a future can be
+    /// pin-projected from a containing struct, for example using `pin-project`,
+    /// and then polled through this without `unsafe` code.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn as_pin_mut(this: Pin<&mut Self>) -> Pin<&mut T> {
+        this.check();
+        unsafe { this.map_unchecked_mut(|s| &mut *s.value) }
+    }
+
+    /// Projects a pinned shared reference to the binding to a pinned shared reference to the value.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn as_pin_ref(this: Pin<&Self>) -> Pin<&T> {
+        this.check();
+        unsafe { this.map_unchecked(|s| &*s.value) }
+    }
+}
+
+impl<T> ThreadBound<T, Lenient> {
+    /// A reference to the value, if called from the owner thread.
+    #[track_caller]
+    pub fn get(this: &Self) -> Result<&T, WrongThread> {
+        Self::try_get(this)
+    }
+
+    /// A mutable reference to the value, if called from the owner thread.
+    #[track_caller]
+    pub fn get_mut(this: &mut Self) -> Result<&mut T, WrongThread> {
+        Self::try_get_mut(this)
+    }
+}
+
+impl<T, P> Deref for ThreadBound<T, P>
+where
+    P: Panicking,
+{
     type Target = T;
     #[track_caller]
     fn deref(&self) -> &T {
@@ -91,7 +726,10 @@ impl<T> Deref for ThreadBound<T> {
     }
 }
 
-impl<T> DerefMut for ThreadBound<T> {
+impl<T, P> DerefMut for ThreadBound<T, P>
+where
+    P: Panicking,
+{
     #[track_caller]
     fn deref_mut(&mut self) -> &mut T {
         self.check();
@@ -99,14 +737,24 @@ impl<T> DerefMut for ThreadBound<T> {
     }
 }
 
-impl<T> fmt::Debug for ThreadBound<T>
+// The other policies are receivers because they dereference to the value.
+#[cfg(feature = "nightly")]
+impl<T> std::ops::Receiver for ThreadBound<T, Lenient> {
+    type Target = T;
+}
+
+impl<T, P> fmt::Debug for ThreadBound<T, P>
 where
     T: fmt::Debug,
+    P: Policy,
 {
     #[track_caller]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut d = f.debug_struct("ThreadBound");
         d.field("thread_id", &self.thread_id);
+        if let Some(label) = self.label {
+            d.field("label", &label);
+        }
         if Self::is_usable(self) {
             d.field("value", &self.value);
         }
@@ -114,9 +762,10 @@ where
     }
 }
 
-impl<T> fmt::Display for ThreadBound<T>
+impl<T, P> fmt::Display for ThreadBound<T, P>
 where
     T: fmt::Display,
+    P: Panicking,
 {
     #[track_caller]
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
@@ -125,28 +774,76 @@ where
     }
 }
 
-impl<T> Default for ThreadBound<T>
+impl<T> ThreadBound<T>
+where
+    T: fmt::Display,
+{
+    /// Caches the display output of the value, so that it can be displayed from any thread.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn with_cached_display(this: Self) -> DisplayBound<T> {
+        DisplayBound::from_bound(this)
+    }
+}
+
+impl<T> ThreadBound<T>
+where
+    T: SendProjection,
+{
+    /// Caches the [projection](SendProjection) of the value, so that it can be read from any thread.
+    ///
+    /// The cached projection is updated by [`SnapshotBound::refresh`].
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn with_cached_projection(this: Self) -> SnapshotBound<T, T::Out> {
+        SnapshotBound::from_bound(this, T::project)
+    }
+}
+
+impl<T, P> Default for ThreadBound<T, P>
 where
     T: Default,
+    P: Policy,
 {
     #[track_caller]
     fn default() -> Self {
-        Self::new(T::default())
+        Self::with_policy(T::default())
+    }
+}
+
+impl<T, P, A> FromIterator<A> for ThreadBound<T, P>
+where
+    T: FromIterator<A>,
+    P: Policy,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = A>,
+    {
+        Self::with_policy(T::from_iter(iter))
     }
 }
 
-impl<T> Clone for ThreadBound<T>
+impl<T, P> Clone for ThreadBound<T, P>
 where
     T: Clone,
+    P: Panicking,
 {
     #[track_caller]
     fn clone(&self) -> Self {
         self.check();
-        Self { thread_id: self.thread_id, value: self.value.clone(), taken: self.taken }
+        Self::bind((*self.value).clone(), self.label)
     }
 }
 
-impl<T> std::borrow::Borrow<T> for ThreadBound<T> {
+impl<T, P> std::borrow::Borrow<T> for ThreadBound<T, P>
+where
+    P: Panicking,
+{
     #[track_caller]
     fn borrow(&self) -> &T {
         self.check();
@@ -154,7 +851,10 @@ impl<T> std::borrow::Borrow<T> for ThreadBound<T> {
     }
 }
 
-impl<T> std::borrow::BorrowMut<T> for ThreadBound<T> {
+impl<T, P> std::borrow::BorrowMut<T> for ThreadBound<T, P>
+where
+    P: Panicking,
+{
     #[track_caller]
     fn borrow_mut(&mut self) -> &mut T {
         self.check();
@@ -162,21 +862,23 @@ impl<T> std::borrow::BorrowMut<T> for ThreadBound<T> {
     }
 }
 
-impl<T> PartialEq for ThreadBound<T>
+impl<T, P> PartialEq for ThreadBound<T, P>
 where
     T: PartialEq,
+    P: Panicking,
 {
     #[track_caller]
-    fn eq(&self, other: &ThreadBound<T>) -> bool {
+    fn eq(&self, other: &ThreadBound<T, P>) -> bool {
         self.check();
         other.check();
         self.value.eq(&other.value)
     }
 }
 
-impl<T> PartialEq<T> for ThreadBound<T>
+impl<T, P> PartialEq<T> for ThreadBound<T, P>
 where
     T: PartialEq,
+    P: Panicking,
 {
     #[track_caller]
     fn eq(&self, other: &T) -> bool {
@@ -185,23 +887,30 @@ where
     }
 }
 
-impl<T> Eq for ThreadBound<T> where T: Eq {}
+impl<T, P> Eq for ThreadBound<T, P>
+where
+    T: Eq,
+    P: Panicking,
+{
+}
 
-impl<T> PartialOrd for ThreadBound<T>
+impl<T, P> PartialOrd for ThreadBound<T, P>
 where
     T: PartialOrd,
+    P: Panicking,
 {
     #[track_caller]
-    fn partial_cmp(&self, other: &ThreadBound<T>) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &ThreadBound<T, P>) -> Option<std::cmp::Ordering> {
         self.check();
         other.check();
         self.value.partial_cmp(&other.value)
     }
 }
 
-impl<T> PartialOrd<T> for ThreadBound<T>
+impl<T, P> PartialOrd<T> for ThreadBound<T, P>
 where
     T: PartialOrd,
+    P: Panicking,
 {
     #[track_caller]
     fn partial_cmp(&self, other: &T) -> Option<std::cmp::Ordering> {
@@ -210,21 +919,23 @@ where
     }
 }
 
-impl<T> Ord for ThreadBound<T>
+impl<T, P> Ord for ThreadBound<T, P>
 where
     T: Ord,
+    P: Panicking,
 {
     #[track_caller]
-    fn cmp(&self, other: &ThreadBound<T>) -> std::cmp::Ordering {
+    fn cmp(&self, other: &ThreadBound<T, P>) -> std::cmp::Ordering {
         self.check();
         other.check();
         self.value.cmp(&other.value)
     }
 }
 
-impl<T> std::hash::Hash for ThreadBound<T>
+impl<T, P> std::hash::Hash for ThreadBound<T, P>
 where
     T: std::hash::Hash,
+    P: Panicking,
 {
     #[track_caller]
     fn hash<H>(&self, state: &mut H)
@@ -236,75 +947,285 @@ where
     }
 }
 
-impl<T> Drop for ThreadBound<T> {
+impl<T, P> Drop for ThreadBound<T, P>
+where
+    P: Policy,
+{
     #[track_caller]
     fn drop(&mut self) {
-        if needs_drop::<T>() && !self.taken {
-            self.check();
-            unsafe { ManuallyDrop::drop(&mut self.value) };
+        liveness::release(self.thread_id);
+        #[cfg(feature = "diagnostics")]
+        if let Some(id) = self.live.take() {
+            live::unregister(id);
+        }
+
+        if self.taken || (!needs_drop::<T>() && self.on_drop.is_none()) {
+            return;
+        }
+
+        if !Self::is_usable(self) {
+            // The hook may be `!Send`, so it must not be dropped here, not even by the unwinding of `check`.
+            mem::forget(self.on_drop.take());
+            if P::LEAK_FOREIGN_DROP {
+                return;
+            }
+        }
+
+        self.check();
+        if let Some(hook) = self.on_drop.take() {
+            hook(&mut self.value, self.drop_context);
         }
+        unsafe { ManuallyDrop::drop(&mut self.value) };
     }
 }
 
-impl<T> Future for ThreadBound<T>
+impl<T, P> Future for ThreadBound<T, P>
 where
     T: Future,
+    P: Panicking,
 {
     type Output = T::Output;
 
     #[track_caller]
     fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
-        self.check();
-        let future = unsafe { self.map_unchecked_mut(|s| &mut *s.value) };
-        future.poll(cx)
+        Self::as_pin_mut(self).poll(cx)
+    }
+}
+
+impl<T> ThreadBound<T>
+where
+    T: 'static,
+{
+    /// Constructs a value on the thread of the porter using the [`Send`] factory
+    /// and binds it to that thread.
+    ///
+    /// The returned future resolves to the binding, which can be moved anywhere
+    /// but only used by the thread of the porter.
+    /// This allows setting up resources for a thread that is not the current one.
+    /// If the returned future is dropped after the value has been constructed but before
+    /// it has resolved, the value is leaked unless dropped on the thread of the porter.
+    ///
+    /// ### Panics
+    /// Panics if the porter has been dropped.
+    /// Awaiting the returned future panics if the porter is dropped before
+    /// the factory has been executed.
+    /// A panic raised by the factory is propagated to the thread awaiting the returned future.
+    #[track_caller]
+    pub fn new_on<F>(owner: &PorterHandle, factory: F) -> impl Future<Output = Self> + Send + 'static
+    where
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let call = owner.call(move || ThreadBound::<T, Leaky>::with_policy(factory()));
+        async move { ThreadBound::into_policy(call.await) }
+    }
+
+    /// Drops the value on the owner thread.
+    ///
+    /// When called from another thread, the drop is forwarded to the porter
+    /// [registered](crate::Porter::register) for the owner thread, so that
+    /// the porter does not need to be passed around.
+    /// The value is leaked if that porter is dropped before handling it.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread and no
+    /// porter is registered for the owner thread.
+    #[track_caller]
+    pub fn drop_on_owner(this: Self) {
+        if Self::is_usable(&this) {
+            drop(this);
+            return;
+        }
+
+        match Self::porter(&this) {
+            Some(porter) => porter.drop_bound(this),
+            None => panic!("no porter is registered for thread {:?}", this.thread_id),
+        }
+    }
+
+    /// Disposes of the value by running the asynchronous cleanup `dispose` on the owner thread.
+    ///
+    /// The cleanup is driven by the porter of the owner thread and the returned
+    /// future resolves when it has completed.
+    /// If the porter has been dropped, the value is leaked and the future resolves immediately.
+    ///
+    /// ### Panics
+    /// Panics if the porter does not belong to the owner thread.
+    /// A panic raised by the cleanup is propagated to the thread polling the returned future.
+    #[track_caller]
+    pub fn async_dispose<F, Fut>(
+        this: Self, porter: &PorterHandle, dispose: F,
+    ) -> impl Future<Output = ()> + Send + 'static
+    where
+        F: FnOnce(T) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + 'static,
+    {
+        assert_eq!(porter.thread_id(), this.thread_id, "porter must belong to the owner thread");
+        let handle = porter.spawn_bypass(move || dispose(ThreadBound::into_inner(this)));
+        async move {
+            if let Some(handle) = handle {
+                if let Err(JoinError::Panicked(payload)) = handle.await {
+                    std::panic::resume_unwind(payload);
+                }
+            }
+        }
+    }
+}
+
+impl<T, P> ThreadBound<T, P>
+where
+    P: Policy,
+{
+    /// Executes `f` with a reference to the value on the owner thread, blocking the
+    /// calling thread until it has completed.
+    ///
+    /// The closure is executed by the porter [registered](crate::Porter::register) for the
+    /// owner thread, or inline when called from the owner thread, since waiting would deadlock.
+    /// See [`PorterHandle::call_blocking`] for details.
+    ///
+    /// Returns [`CallError::NoPorter`] if no porter is registered for the owner thread
+    /// and [`CallError::Closed`] if it is dropped before the closure has completed.
+    ///
+    /// ### Panics
+    /// Panics if the value [has been taken](Self::is_taken).
+    #[track_caller]
+    pub fn invoke_blocking<R>(this: &Self, f: impl FnOnce(&T) -> R + Send) -> Result<R, CallError>
+    where
+        R: Send,
+    {
+        if this.try_check().is_ok() {
+            return Ok(f(&this.value));
+        }
+
+        let porter = Self::porter(this).ok_or(CallError::NoPorter)?;
+        porter.call_blocking(move || {
+            let this = this;
+            this.check();
+            f(&this.value)
+        })
+    }
+
+    /// The porter [registered](crate::Porter::register) for the owner thread.
+    ///
+    /// This can be called from any thread.
+    pub fn porter(this: &Self) -> Option<PorterHandle> {
+        PorterHandle::for_thread(this.thread_id)
+    }
+}
+
+impl<T> ThreadBound<T>
+where
+    T: Future + 'static,
+    T::Output: Send + 'static,
+{
+    /// Converts the bound future into a future that can be polled from any thread.
+    ///
+    /// Polling is forwarded to the porter of the owner thread, which drives the
+    /// inner future and proxies wake-ups back to the polling thread.
+    ///
+    /// ### Panics
+    /// Panics if the porter does not belong to the owner thread.
+    #[track_caller]
+    pub fn into_dispatched_future(this: Self, porter: &PorterHandle) -> DispatchedFuture<T> {
+        assert_eq!(porter.thread_id(), this.thread_id, "porter must belong to the owner thread");
+        DispatchedFuture::new(this, porter.clone())
+    }
+
+    /// Converts the bound future into a future that is polled in place on the
+    /// owner thread and defers polls from other threads to it instead of panicking.
+    ///
+    /// ### Panics
+    /// Panics if the porter does not belong to the owner thread or if called
+    /// from another thread than the owner thread.
+    #[track_caller]
+    pub fn into_lenient_future(this: Self, porter: &PorterHandle) -> LenientFuture<T> {
+        assert_eq!(porter.thread_id(), this.thread_id, "porter must belong to the owner thread");
+        this.check();
+        LenientFuture::new(this, porter.clone())
     }
 }
 
-impl<T, S> Sink<S> for ThreadBound<T>
+impl<T> ThreadBound<T>
+where
+    T: Stream + 'static,
+    T::Item: Send + 'static,
+{
+    /// Converts the bound stream into a stream that is polled in place on the
+    /// owner thread and defers polls from other threads to it instead of panicking.
+    ///
+    /// Items produced on the owner thread for a foreign poll are buffered until
+    /// the stream is polled again.
+    ///
+    /// ### Panics
+    /// Panics if the porter does not belong to the owner thread or if called
+    /// from another thread than the owner thread.
+    #[track_caller]
+    pub fn into_lenient_stream(this: Self, porter: &PorterHandle) -> LenientStream<T> {
+        assert_eq!(porter.thread_id(), this.thread_id, "porter must belong to the owner thread");
+        this.check();
+        LenientStream::new(this, porter.clone())
+    }
+}
+
+impl<T> ThreadBound<T>
+where
+    T: 'static,
+{
+    /// Converts the bound sink into a sink that is driven in place on the
+    /// owner thread and forwards items sent from other threads to it instead of panicking.
+    ///
+    /// ### Panics
+    /// Panics if the porter does not belong to the owner thread or if called
+    /// from another thread than the owner thread.
+    #[track_caller]
+    pub fn into_lenient_sink<I>(this: Self, porter: &PorterHandle) -> LenientSink<T, I>
+    where
+        T: Sink<I>,
+        T::Error: Send + 'static,
+        I: Send + 'static,
+    {
+        assert_eq!(porter.thread_id(), this.thread_id, "porter must belong to the owner thread");
+        this.check();
+        LenientSink::new(this, porter.clone())
+    }
+}
+
+impl<T, P, S> Sink<S> for ThreadBound<T, P>
 where
     T: Sink<S>,
+    P: Panicking,
 {
     type Error = T::Error;
 
     #[track_caller]
     fn poll_ready(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
-        self.check();
-        let sink = unsafe { self.map_unchecked_mut(|s| &mut *s.value) };
-        sink.poll_ready(cx)
+        Self::as_pin_mut(self).poll_ready(cx)
     }
 
     #[track_caller]
     fn start_send(self: Pin<&mut Self>, item: S) -> Result<(), Self::Error> {
-        self.check();
-        let sink = unsafe { self.map_unchecked_mut(|s| &mut *s.value) };
-        sink.start_send(item)
+        Self::as_pin_mut(self).start_send(item)
     }
 
     #[track_caller]
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
-        self.check();
-        let sink = unsafe { self.map_unchecked_mut(|s| &mut *s.value) };
-        sink.poll_flush(cx)
+        Self::as_pin_mut(self).poll_flush(cx)
     }
 
     #[track_caller]
     fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
-        self.check();
-        let sink = unsafe { self.map_unchecked_mut(|s| &mut *s.value) };
-        sink.poll_close(cx)
+        Self::as_pin_mut(self).poll_close(cx)
     }
 }
 
-impl<T> Stream for ThreadBound<T>
+impl<T, P> Stream for ThreadBound<T, P>
 where
     T: Stream,
+    P: Panicking,
 {
     type Item = T::Item;
 
     #[track_caller]
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        self.check();
-        let stream = unsafe { self.map_unchecked_mut(|s| &mut *s.value) };
-        stream.poll_next(cx)
+        Self::as_pin_mut(self).poll_next(cx)
     }
 }
@@ -0,0 +1,133 @@
+//! Timer thread for deadlines of remote calls.
+
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    sync::{Condvar, Mutex, MutexGuard, OnceLock, PoisonError},
+    task::{Context, Poll, Waker},
+    thread,
+    time::Instant,
+};
+
+#[derive(Default)]
+struct Entries {
+    wakers: BTreeMap<(Instant, u64), Waker>,
+    seq: u64,
+}
+
+#[derive(Default)]
+struct Timer {
+    entries: Mutex<Entries>,
+    changed: Condvar,
+}
+
+impl Timer {
+    fn get() -> &'static Timer {
+        static TIMER: OnceLock<Timer> = OnceLock::new();
+        TIMER.get_or_init(|| {
+            thread::Builder::new()
+                .name("threadporter-timer".into())
+                .spawn(|| Timer::get().run())
+                .expect("cannot spawn timer thread");
+            Timer::default()
+        })
+    }
+
+    fn entries(&self) -> MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Registers the waker for the deadline or updates the waker of an existing registration.
+    ///
+    /// Returns the sequence number identifying the registration.
+    fn register(&self, deadline: Instant, seq: Option<u64>, waker: &Waker) -> u64 {
+        let mut entries = self.entries();
+        if let Some(seq) = seq {
+            if let Some(registered) = entries.wakers.get_mut(&(deadline, seq)) {
+                if !registered.will_wake(waker) {
+                    registered.clone_from(waker);
+                }
+                return seq;
+            }
+        }
+
+        entries.seq += 1;
+        let seq = entries.seq;
+        entries.wakers.insert((deadline, seq), waker.clone());
+        self.changed.notify_one();
+        seq
+    }
+
+    /// Removes a registration, if it has not fired yet.
+    fn deregister(&self, deadline: Instant, seq: u64) {
+        let waker = self.entries().wakers.remove(&(deadline, seq));
+        drop(waker);
+    }
+
+    fn run(&self) {
+        loop {
+            let mut entries = self.entries();
+            let now = Instant::now();
+            let mut expired = Vec::new();
+            while let Some(entry) = entries.wakers.first_entry() {
+                if entry.key().0 > now {
+                    break;
+                }
+                expired.push(entry.remove());
+            }
+
+            if expired.is_empty() {
+                match entries.wakers.keys().next() {
+                    Some(&(deadline, _)) => drop(self.changed.wait_timeout(entries, deadline - now)),
+                    None => drop(self.changed.wait(entries)),
+                }
+            } else {
+                drop(entries);
+                expired.into_iter().for_each(Waker::wake);
+            }
+        }
+    }
+}
+
+/// Completes once the deadline has passed.
+///
+/// Requires thread support, since deadlines are tracked by a background thread.
+pub(crate) struct Sleep {
+    deadline: Instant,
+    seq: Option<u64>,
+}
+
+impl Sleep {
+    pub(crate) fn until(deadline: Instant) -> Self {
+        Self { deadline, seq: None }
+    }
+
+    /// Whether the deadline has passed.
+    pub(crate) fn is_elapsed(&self) -> bool {
+        Instant::now() >= self.deadline
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        if self.is_elapsed() {
+            if let Some(seq) = self.seq.take() {
+                Timer::get().deregister(self.deadline, seq);
+            }
+            return Poll::Ready(());
+        }
+        self.seq = Some(Timer::get().register(self.deadline, self.seq, cx.waker()));
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let Some(seq) = self.seq {
+            Timer::get().deregister(self.deadline, seq);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Recording of recent accesses to thread-bound values for debugging.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use std::{
+    collections::VecDeque,
+    fmt,
+    panic::Location,
+    sync::{Mutex, PoisonError},
+    thread,
+    thread::Thread,
+};
+
+/// An access to a thread-bound value recorded by its access trace.
+///
+/// See [`ThreadBound::trace_accesses`](crate::ThreadBound::trace_accesses).
+#[derive(Clone, Debug)]
+pub struct Access {
+    /// The location of the code that accessed the value.
+    pub location: &'static Location<'static>,
+    /// The thread that accessed the value.
+    pub thread: Thread,
+    /// When the value was accessed.
+    ///
+    /// This is not recorded on WebAssembly targets, where the system clock cannot be read
+    /// through [`Instant`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub time: Instant,
+}
+
+impl fmt::Display for Access {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.thread.id())?;
+        if let Some(name) = self.thread.name() {
+            write!(f, " ({name})")?;
+        }
+        write!(f, " at {}", self.location)
+    }
+}
+
+/// Ring buffer holding the most recent accesses to a value.
+pub(crate) struct AccessTrace {
+    capacity: usize,
+    accesses: Mutex<VecDeque<Access>>,
+}
+
+impl AccessTrace {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self { capacity, accesses: Mutex::new(VecDeque::with_capacity(capacity)) }
+    }
+
+    pub(crate) fn record(&self, location: &'static Location<'static>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let access = Access {
+            location,
+            thread: thread::current(),
+            #[cfg(not(target_arch = "wasm32"))]
+            time: Instant::now(),
+        };
+        let mut accesses = self.accesses.lock().unwrap_or_else(PoisonError::into_inner);
+        if accesses.len() == self.capacity {
+            accesses.pop_front();
+        }
+        accesses.push_back(access);
+    }
+
+    pub(crate) fn accesses(&self) -> Vec<Access> {
+        self.accesses.lock().unwrap_or_else(PoisonError::into_inner).iter().cloned().collect()
+    }
+}
+
+impl fmt::Display for AccessTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let accesses = self.accesses.lock().unwrap_or_else(PoisonError::into_inner);
+        if accesses.is_empty() {
+            return Ok(());
+        }
+        writeln!(f)?;
+        write!(f, "recent accesses (oldest first):")?;
+        for access in accesses.iter() {
+            write!(f, "\n  {access}")?;
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(last) = accesses.back() {
+                write!(f, ", {:?} before the last access", last.time.duration_since(access.time))?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+//! Marshal wake-ups to the owner thread of a waker.
+
+use std::{
+    fmt,
+    mem::ManuallyDrop,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Wake, Waker},
+    thread,
+    thread::Thread,
+};
+
+use crate::{PorterHandle, ThreadBound};
+
+enum Route {
+    Porter(PorterHandle),
+    Unpark(Thread),
+}
+
+struct Inner {
+    waker: ManuallyDrop<ThreadBound<Waker>>,
+    route: Route,
+    pending: AtomicBool,
+}
+
+impl Inner {
+    fn forward(self: &Arc<Self>) {
+        if ThreadBound::is_usable(&self.waker) {
+            self.waker.wake_by_ref();
+            return;
+        }
+
+        match &self.route {
+            Route::Porter(porter) => {
+                if !self.pending.swap(true, Ordering::AcqRel) {
+                    let this = self.clone();
+                    porter.post_bypass(move || {
+                        this.pending.store(false, Ordering::Release);
+                        this.waker.wake_by_ref();
+                    });
+                }
+            }
+            Route::Unpark(thread) => {
+                self.pending.store(true, Ordering::Release);
+                thread.unpark();
+            }
+        }
+    }
+}
+
+impl Wake for Inner {
+    fn wake(self: Arc<Self>) {
+        self.forward()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.forward()
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        let waker = unsafe { ManuallyDrop::take(&mut self.waker) };
+        match &self.route {
+            Route::Porter(porter) => porter.drop_bound(waker),
+            Route::Unpark(_) if ThreadBound::is_usable(&waker) => drop(waker),
+            Route::Unpark(_) => std::mem::forget(waker),
+        }
+    }
+}
+
+/// Proxies wake-ups from any thread to a waker that must only be used on its owner thread.
+///
+/// Wakers of single-threaded executors may not tolerate being woken from
+/// foreign threads.
+/// A bound waker keeps such a waker on its owner thread and provides a
+/// thread-safe [`Waker`] that marshals wake-ups to it, either through a
+/// [`Porter`](crate::Porter) or by setting a flag and unparking the owner thread.
+pub struct BoundWaker {
+    inner: Arc<Inner>,
+}
+
+impl BoundWaker {
+    /// Binds the waker to the current thread and routes foreign wake-ups through the porter.
+    ///
+    /// Wake-ups arriving while a previous one is still queued are coalesced.
+    /// They are ignored once the porter has been dropped.
+    ///
+    /// ### Panics
+    /// Panics if the porter does not belong to the current thread.
+    #[track_caller]
+    pub fn new(waker: Waker, porter: &PorterHandle) -> Self {
+        assert!(porter.is_owner(), "porter must belong to the current thread");
+        Self::with_route(waker, Route::Porter(porter.clone()))
+    }
+
+    /// Binds the waker to the current thread and signals foreign wake-ups by
+    /// setting a flag and unparking the current thread.
+    ///
+    /// The owner thread must call [`dispatch`](Self::dispatch) after being unparked.
+    /// If the last reference to the bound waker is dropped on a foreign thread,
+    /// the inner waker is leaked.
+    pub fn parking(waker: Waker) -> Self {
+        Self::with_route(waker, Route::Unpark(thread::current()))
+    }
+
+    fn with_route(waker: Waker, route: Route) -> Self {
+        let inner =
+            Inner { waker: ManuallyDrop::new(ThreadBound::new(waker)), route, pending: AtomicBool::new(false) };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// A thread-safe waker that proxies wake-ups to the owner thread.
+    pub fn waker(&self) -> Waker {
+        Waker::from(self.inner.clone())
+    }
+
+    /// Wakes the inner waker if a foreign wake-up has been signalled since the last call.
+    ///
+    /// This is only needed for [parking](Self::parking) bound wakers and returns
+    /// whether a wake-up was pending.
+    ///
+    /// ### Panics
+    /// Panics if called from another thread than the owner thread.
+    #[track_caller]
+    pub fn dispatch(&self) -> bool {
+        let waker = &**self.inner.waker;
+        let pending = self.inner.pending.swap(false, Ordering::AcqRel);
+        if pending {
+            waker.wake_by_ref();
+        }
+        pending
+    }
+}
+
+impl Clone for BoundWaker {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl fmt::Debug for BoundWaker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundWaker").field("thread_id", &ThreadBound::thread_id(&self.inner.waker)).finish()
+    }
+}
@@ -0,0 +1,175 @@
+use std::{
+    future::Future,
+    pin::pin,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+    time::Duration,
+};
+
+use threadporter::{Porter, ThreadBound};
+
+fn poll_once<F: Future>(future: F) -> Poll<F::Output> {
+    pin!(future).poll(&mut Context::from_waker(Waker::noop()))
+}
+
+/// Records the order in which closures are executed.
+#[derive(Clone, Default)]
+struct Log(Arc<Mutex<Vec<u32>>>);
+
+impl Log {
+    fn push(&self, id: u32) -> impl FnOnce() + Send + 'static {
+        let log = self.clone();
+        move || log.0.lock().unwrap().push(id)
+    }
+
+    fn take(&self) -> Vec<u32> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+fn foreign(f: impl FnOnce() + Send) {
+    thread::scope(|s| s.spawn(f).join().unwrap());
+}
+
+#[test]
+fn block_waits_for_space() {
+    let porter = Porter::bounded(1);
+    let handle = porter.handle();
+    let log = Log::default();
+
+    thread::scope(|s| {
+        let producer = s.spawn(|| {
+            handle.post(log.push(1));
+            handle.post(log.push(2));
+        });
+        while handle.queue_len() == 0 {
+            thread::yield_now();
+        }
+        thread::sleep(Duration::from_millis(50));
+        assert!(!producer.is_finished());
+        assert_eq!(handle.queue_len(), 1);
+
+        while !producer.is_finished() {
+            porter.run_until_idle();
+            thread::yield_now();
+        }
+    });
+    porter.run_until_idle();
+    assert_eq!(log.take(), [1, 2]);
+}
+
+#[test]
+fn owner_bypasses_capacity() {
+    let porter = Porter::bounded(1);
+    let handle = porter.handle();
+    let log = Log::default();
+
+    handle.post(log.push(1));
+    handle.post(log.push(2));
+    assert_eq!(handle.queue_len(), 2);
+
+    porter.run_until_idle();
+    assert_eq!(log.take(), [1, 2]);
+}
+
+#[test]
+fn forwarded_drop_bypasses_capacity() {
+    let porter = Porter::bounded(1);
+    porter.register();
+    let handle = porter.handle();
+    let bound = ThreadBound::new(Rc::new(()));
+    let weak = Rc::downgrade(&bound);
+
+    foreign(|| {
+        handle.post(|| ());
+        ThreadBound::drop_on_owner(bound);
+    });
+    assert_eq!(handle.queue_len(), 2);
+
+    porter.run_until_idle();
+    assert_eq!(weak.strong_count(), 0);
+}
+
+#[test]
+fn lossy_evicts_oldest_lossy() {
+    let porter = Porter::bounded(2);
+    let handle = porter.handle();
+    let log = Log::default();
+
+    foreign(|| {
+        handle.post_lossy(log.push(1));
+        handle.post_lossy(log.push(2));
+        handle.post_lossy(log.push(3));
+    });
+    assert_eq!(handle.queue_len(), 2);
+
+    porter.run_until_idle();
+    assert_eq!(log.take(), [2, 3]);
+}
+
+#[test]
+fn lossy_dropped_when_full_of_regular() {
+    let porter = Porter::bounded(1);
+    let handle = porter.handle();
+    let log = Log::default();
+
+    foreign(|| {
+        handle.post(log.push(1));
+        handle.post_lossy(log.push(2));
+    });
+    assert_eq!(handle.queue_len(), 1);
+
+    porter.run_until_idle();
+    assert_eq!(log.take(), [1]);
+}
+
+#[test]
+fn permit_uses_reserved_slot() {
+    let porter = Porter::bounded(1);
+    let handle = porter.handle();
+    let log = Log::default();
+
+    foreign(|| {
+        let Poll::Ready(permit) = poll_once(handle.reserve()) else { panic!("no slot available") };
+        assert!(poll_once(handle.reserve()).is_pending());
+        assert!(handle.poll_ready(&mut Context::from_waker(Waker::noop())).is_pending());
+
+        permit.post(log.push(1));
+        assert_eq!(handle.queue_len(), 1);
+        assert!(poll_once(handle.reserve()).is_pending());
+    });
+
+    porter.run_until_idle();
+    assert_eq!(log.take(), [1]);
+    foreign(|| assert!(poll_once(handle.reserve()).is_ready()));
+}
+
+#[test]
+fn dropped_permit_releases_slot() {
+    let porter = Porter::bounded(1);
+    let handle = porter.handle();
+
+    foreign(|| {
+        let Poll::Ready(permit) = poll_once(handle.reserve()) else { panic!("no slot available") };
+        assert!(poll_once(handle.reserve()).is_pending());
+
+        drop(permit);
+        assert!(handle.poll_ready(&mut Context::from_waker(Waker::noop())).is_ready());
+        assert!(poll_once(handle.reserve()).is_ready());
+    });
+    assert_eq!(handle.queue_len(), 0);
+}
+
+#[test]
+fn permit_call_releases_slot_on_owner() {
+    let porter = Porter::bounded(1);
+    let handle = porter.handle();
+
+    let Poll::Ready(permit) = poll_once(handle.reserve()) else { panic!("no slot available") };
+    assert_eq!(poll_once(permit.call(|| 1)), Poll::Ready(1));
+    assert_eq!(handle.queue_len(), 0);
+
+    foreign(|| assert!(poll_once(handle.reserve()).is_ready()));
+}
@@ -0,0 +1,27 @@
+use threadporter::{BoundArena, Lenient};
+
+#[test]
+fn ids_stop_resolving_after_clear() {
+    let mut arena = BoundArena::new();
+    let id = arena.alloc(1);
+    arena.clear();
+
+    let next = arena.alloc(2);
+    assert_eq!(arena.get(id), None);
+    assert_eq!(arena.get(next), Some(&2));
+}
+
+#[test]
+fn id_of_other_arena_is_rejected() {
+    let mut first = BoundArena::new();
+    let mut second = BoundArena::new();
+    let id = first.alloc(1);
+    second.alloc(2);
+
+    assert_eq!(second.get(id), None);
+    assert_eq!(second.get_mut(id), None);
+    assert_eq!(first.get(id), Some(&1));
+
+    let lenient = BoundArena::<i32, Lenient>::with_policy();
+    assert_eq!(lenient.try_get(id), Ok(None));
+}
@@ -0,0 +1,132 @@
+use std::{
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread::{self, ThreadId},
+};
+
+use threadporter::Porter;
+
+fn poll_once<F: Future>(future: F) -> Poll<F::Output> {
+    pin!(future).poll(&mut Context::from_waker(Waker::noop()))
+}
+
+/// Records the order in which closures are executed.
+#[derive(Clone, Default)]
+struct Log(Arc<Mutex<Vec<u32>>>);
+
+impl Log {
+    fn push(&self, id: u32) -> impl FnOnce() + Send + 'static {
+        let log = self.clone();
+        move || log.0.lock().unwrap().push(id)
+    }
+
+    fn take(&self) -> Vec<u32> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+/// Records the thread it is dropped on.
+struct DropThread(Arc<Mutex<Option<ThreadId>>>);
+
+impl Drop for DropThread {
+    fn drop(&mut self) {
+        *self.0.lock().unwrap() = Some(thread::current().id());
+    }
+}
+
+#[test]
+fn coalesced_collapse_to_latest() {
+    let porter = Porter::new();
+    let handle = porter.handle();
+    let log = Log::default();
+    let dropped_on = Arc::new(Mutex::new(None));
+
+    let producer = thread::scope(|s| {
+        s.spawn(|| {
+            handle.post(log.push(1));
+            let replaced = DropThread(dropped_on.clone());
+            handle.post_coalesced(7, move || drop(replaced));
+            handle.post(log.push(3));
+            handle.post_coalesced(7, log.push(4));
+            handle.post_coalesced(8, log.push(5));
+            thread::current().id()
+        })
+        .join()
+        .unwrap()
+    });
+    assert_eq!(handle.queue_len(), 4);
+    assert_eq!(*dropped_on.lock().unwrap(), Some(producer));
+
+    porter.run_until_idle();
+    assert_eq!(log.take(), [1, 4, 3, 5]);
+
+    handle.post_coalesced(7, log.push(6));
+    handle.post_coalesced(7, log.push(7));
+    porter.run_until_idle();
+    assert_eq!(log.take(), [7]);
+}
+
+#[test]
+fn batch_occupies_one_slot() {
+    let porter = Porter::new();
+    let handle = porter.handle();
+    let log = Log::default();
+
+    let mut batch = handle.batch();
+    batch.post(log.push(1));
+    let call = batch.call(|| 2);
+    batch.post(log.push(3));
+    assert_eq!(batch.len(), 3);
+    batch.submit();
+    assert_eq!(handle.queue_len(), 1);
+
+    porter.run_until_idle();
+    assert_eq!(log.take(), [1, 3]);
+    assert_eq!(poll_once(call), Poll::Ready(2));
+}
+
+#[test]
+fn batch_skips_dropped_calls() {
+    let porter = Porter::new();
+    let handle = porter.handle();
+    let log = Log::default();
+
+    let mut batch = handle.batch();
+    let dropped = batch.call(log.push(1));
+    let kept = batch.call(log.push(2));
+    batch.submit();
+    drop(dropped);
+
+    porter.run_until_idle();
+    assert_eq!(log.take(), [2]);
+    assert_eq!(poll_once(kept), Poll::Ready(()));
+}
+
+#[test]
+fn batch_continues_after_panic() {
+    let porter = Porter::new();
+    let handle = porter.handle();
+    let log = Log::default();
+
+    let mut batch = handle.batch();
+    let panicking = batch.call(|| panic!("closure panicked"));
+    batch.post(log.push(1));
+    batch.submit();
+
+    porter.run_until_idle();
+    assert_eq!(log.take(), [1]);
+    assert!(catch_unwind(AssertUnwindSafe(|| poll_once(panicking))).is_err());
+}
+
+#[test]
+fn unsubmitted_batch_fails_calls() {
+    let porter = Porter::new();
+    let mut batch = porter.handle().batch();
+    let call = batch.call(|| ());
+    drop(batch);
+
+    assert!(catch_unwind(AssertUnwindSafe(|| poll_once(call))).is_err());
+}
@@ -0,0 +1,119 @@
+use std::{
+    future::Future,
+    pin::pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use threadporter::{CallError, Porter, PorterHandle, Shutdown};
+
+/// Records when it is dropped, borrowing the flag from the stack of the caller.
+struct Flag<'a>(&'a AtomicBool);
+
+impl Drop for Flag<'_> {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Calls `f` blocking from another thread while `owner` acts on the porter,
+/// and checks that the captures of the closure were dropped before the call returned.
+fn call_blocking<R>(
+    porter: Porter, f: impl FnOnce() -> R + Send, owner: impl FnOnce(Porter),
+) -> Result<R, CallError>
+where
+    R: Send,
+{
+    let handle = porter.handle();
+    let dropped = AtomicBool::new(false);
+
+    thread::scope(|s| {
+        let caller = s.spawn(|| {
+            let flag = Flag(&dropped);
+            let result = handle.call_blocking(move || {
+                let _flag = &flag;
+                f()
+            });
+            assert!(dropped.load(Ordering::SeqCst), "captures outlived call_blocking");
+            result
+        });
+        while handle.queue_len() == 0 && !caller.is_finished() {
+            thread::yield_now();
+        }
+        owner(porter);
+        caller.join().unwrap()
+    })
+}
+
+fn run(porter: Porter) {
+    porter.run_until_idle();
+}
+
+fn wait_for(handle: &PorterHandle) {
+    while handle.queue_len() == 0 {
+        thread::yield_now();
+    }
+}
+
+#[test]
+fn borrowed_closure() {
+    let data = vec![1, 2, 3];
+    let data = &data;
+    let result = call_blocking(Porter::new(), move || data.iter().sum::<i32>(), run);
+    assert_eq!(result.unwrap(), 6);
+}
+
+#[test]
+fn panicking_closure() {
+    let result = call_blocking(Porter::new(), || panic!("closure panicked"), run);
+    assert!(matches!(result, Err(CallError::Panicked(_))));
+}
+
+#[test]
+fn cancelled_by_shutdown() {
+    let result = call_blocking(
+        Porter::new(),
+        || unreachable!(),
+        |porter| {
+            wait_for(&porter.handle());
+            let mut shutdown = pin!(porter.shutdown(Shutdown::Cancel));
+            while shutdown.as_mut().poll(&mut Context::from_waker(Waker::noop())) == Poll::Pending {}
+        },
+    );
+    assert!(matches!(result, Err(CallError::Closed)));
+}
+
+#[test]
+fn porter_dropped_while_waiting() {
+    let result = call_blocking(
+        Porter::new(),
+        || unreachable!(),
+        |porter| {
+            wait_for(&porter.handle());
+            drop(porter);
+        },
+    );
+    assert!(matches!(result, Err(CallError::Closed)));
+}
+
+#[test]
+fn porter_dropped_before_call() {
+    let porter = Porter::new();
+    let handle = porter.handle();
+    drop(porter);
+
+    let dropped = AtomicBool::new(false);
+    let flag = Flag(&dropped);
+    let result = thread::scope(|s| {
+        s.spawn(|| {
+            handle.call_blocking(move || {
+                let _flag = &flag;
+            })
+        })
+        .join()
+        .unwrap()
+    });
+    assert!(matches!(result, Err(CallError::Closed)));
+    assert!(dropped.load(Ordering::SeqCst));
+}
@@ -0,0 +1,21 @@
+use std::{cell::Cell, rc::Rc};
+
+use threadporter::{deferred_len, flush_deferred_until, ThreadBound};
+
+#[test]
+fn flush_until_expired() {
+    for _ in 0..3 {
+        ThreadBound::defer_drop(ThreadBound::new(Rc::new(())));
+    }
+
+    let checks = Cell::new(0);
+    let dropped = flush_deferred_until(|| {
+        checks.set(checks.get() + 1);
+        checks.get() > 1
+    });
+    assert_eq!(dropped, 2);
+    assert_eq!(deferred_len(), 1);
+
+    assert_eq!(flush_deferred_until(|| true), 1);
+    assert_eq!(flush_deferred_until(|| true), 0);
+}
@@ -0,0 +1,67 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    thread::ThreadId,
+};
+
+use threadporter::{Leaky, ThreadBound};
+
+/// Counts drops of the capture on other threads than its owner thread.
+struct Capture {
+    owner: ThreadId,
+    foreign_drops: &'static AtomicUsize,
+    _not_send: Rc<()>,
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        if thread::current().id() != self.owner {
+            self.foreign_drops.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+fn capture(foreign_drops: &'static AtomicUsize) -> Capture {
+    Capture { owner: thread::current().id(), foreign_drops, _not_send: Rc::new(()) }
+}
+
+#[test]
+#[cfg_attr(all(feature = "debug-checks-only", not(debug_assertions)), ignore = "thread checks are omitted")]
+fn strict_hook_not_dropped_on_foreign_thread() {
+    static FOREIGN_DROPS: AtomicUsize = AtomicUsize::new(0);
+    let capture = capture(&FOREIGN_DROPS);
+    let bound = ThreadBound::on_drop(ThreadBound::new(1), move |_, _| drop(capture));
+
+    let res = thread::spawn(move || catch_unwind(AssertUnwindSafe(move || drop(bound))).is_err()).join().unwrap();
+    assert!(res, "dropping on a foreign thread must panic");
+    assert_eq!(FOREIGN_DROPS.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn leaky_hook_not_dropped_on_foreign_thread() {
+    static FOREIGN_DROPS: AtomicUsize = AtomicUsize::new(0);
+    let capture = capture(&FOREIGN_DROPS);
+    let bound: ThreadBound<_, Leaky> =
+        ThreadBound::on_drop(ThreadBound::with_policy(1), move |_, _| drop(capture));
+
+    thread::spawn(move || drop(bound)).join().unwrap();
+    assert_eq!(FOREIGN_DROPS.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn hook_runs_on_owner_thread() {
+    static FOREIGN_DROPS: AtomicUsize = AtomicUsize::new(0);
+    let capture = capture(&FOREIGN_DROPS);
+    let called = Rc::new(AtomicUsize::new(0));
+    let counter = called.clone();
+    let bound = ThreadBound::on_drop(ThreadBound::new(1), move |value, _| {
+        counter.fetch_add(*value, Ordering::SeqCst);
+        drop(capture);
+    });
+
+    drop(bound);
+    assert_eq!(called.load(Ordering::SeqCst), 1);
+    assert_eq!(FOREIGN_DROPS.load(Ordering::SeqCst), 0);
+}
@@ -0,0 +1,45 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    rc::Rc,
+    thread,
+};
+
+use threadporter::{BoundGroup, Leaky, Lenient, ThreadBound};
+
+#[test]
+#[cfg_attr(all(feature = "debug-checks-only", not(debug_assertions)), ignore = "thread checks are omitted")]
+fn strict_group_panics_when_dropped_on_foreign_thread() {
+    let group = BoundGroup::new((Rc::new(1), Rc::new(2)));
+    let result = thread::spawn(move || drop(group)).join();
+    assert!(result.is_err());
+}
+
+#[test]
+#[cfg_attr(all(feature = "debug-checks-only", not(debug_assertions)), ignore = "thread checks are omitted")]
+fn leaky_group_is_leaked_on_foreign_thread() {
+    let value = Rc::new(1);
+    let group = BoundGroup::<_, Leaky>::with_policy((value.clone(),));
+    assert_eq!(*group.0, 1);
+
+    thread::spawn(move || {
+        assert!(catch_unwind(AssertUnwindSafe(|| *group.0)).is_err());
+        drop(group);
+    })
+    .join()
+    .unwrap();
+    assert_eq!(Rc::strong_count(&value), 2);
+}
+
+#[test]
+fn lenient_group_never_panics() {
+    let group = BoundGroup::<_, Lenient>::with_policy_named(Rc::new(1), "config");
+    assert_eq!(group.label(), Some("config"));
+    assert!(ThreadBound::get(&group).is_ok());
+
+    thread::spawn(move || {
+        assert!(ThreadBound::get(&group).is_err());
+        drop(group);
+    })
+    .join()
+    .unwrap();
+}
@@ -0,0 +1,43 @@
+use std::{
+    mem,
+    panic::{catch_unwind, AssertUnwindSafe},
+    thread,
+};
+
+use threadporter::{Porter, ThreadBound};
+
+#[test]
+fn invoke_blocking_from_other_thread() {
+    let porter = Porter::install();
+    let bound = ThreadBound::new(vec![1u64, 2, 3]);
+
+    thread::scope(|s| {
+        let invoker = s.spawn(|| ThreadBound::invoke_blocking(&bound, |v| v.iter().sum::<u64>()));
+        while !invoker.is_finished() {
+            porter.run_until_idle();
+            thread::yield_now();
+        }
+        assert_eq!(invoker.join().unwrap().unwrap(), 6);
+    });
+}
+
+#[test]
+fn invoke_blocking_taken() {
+    let porter = Porter::install();
+    let mut bound = ThreadBound::new(vec![1u64, 2, 3]);
+    let mut take = ThreadBound::scope_take(&mut bound);
+    drop(take.take());
+    mem::forget(take);
+
+    let res = catch_unwind(AssertUnwindSafe(|| ThreadBound::invoke_blocking(&bound, |v| v.iter().sum::<u64>())));
+    assert!(res.is_err());
+
+    thread::scope(|s| {
+        let invoker = s.spawn(|| ThreadBound::invoke_blocking(&bound, |v| v.iter().sum::<u64>()));
+        while !invoker.is_finished() {
+            porter.run_until_idle();
+            thread::yield_now();
+        }
+        assert!(invoker.join().is_err());
+    });
+}
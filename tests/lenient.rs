@@ -0,0 +1,28 @@
+use std::{
+    future::{pending, Future},
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::pin,
+    task::{Context, Waker},
+    thread,
+};
+
+use threadporter::{Porter, ThreadBound};
+
+#[test]
+fn foreign_poll_panics_after_porter_closed() {
+    let porter = Porter::new();
+    let future = ThreadBound::into_lenient_future(ThreadBound::new(pending::<()>()), &porter.handle());
+    drop(porter);
+
+    thread::spawn(move || {
+        let mut future = pin!(future);
+        for _ in 0..2 {
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _ = future.as_mut().poll(&mut Context::from_waker(Waker::noop()));
+            }));
+            assert!(result.is_err());
+        }
+    })
+    .join()
+    .unwrap();
+}
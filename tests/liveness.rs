@@ -0,0 +1,44 @@
+use std::{rc::Rc, sync::mpsc, thread};
+
+use threadporter::{Leaky, Lenient, ThreadBound};
+
+#[test]
+fn owner_exit_is_detected() {
+    let bound = thread::spawn(|| {
+        let bound = ThreadBound::<_, Leaky>::with_policy(Rc::new(1));
+        assert!(ThreadBound::is_owner_alive(&bound));
+        bound
+    })
+    .join()
+    .unwrap();
+
+    assert!(!ThreadBound::is_owner_alive(&bound));
+    // SAFETY: the `Rc` is not shared with any other thread.
+    let value = unsafe { ThreadBound::reclaim_if_orphaned(bound) }.unwrap();
+    assert_eq!(*value, 1);
+}
+
+#[test]
+fn exit_is_detected_after_changing_policy() {
+    let bound = thread::spawn(|| ThreadBound::<_, Leaky>::with_policy(Rc::new(1))).join().unwrap();
+    let bound = ThreadBound::into_policy::<Lenient>(bound);
+    assert!(!ThreadBound::is_owner_alive(&bound));
+}
+
+#[test]
+fn exit_is_detected_for_bindings_moved_on_other_threads() {
+    let (bound_tx, bound_rx) = mpsc::channel();
+    let (exit_tx, exit_rx) = mpsc::channel::<()>();
+    let owner = thread::spawn(move || {
+        bound_tx.send(ThreadBound::<_, Leaky>::with_policy(Rc::new(1))).unwrap();
+        let _ = exit_rx.recv();
+    });
+
+    let bound = bound_rx.recv().unwrap();
+    let bound = ThreadBound::into_policy::<Lenient>(bound);
+    assert!(ThreadBound::is_owner_alive(&bound));
+
+    drop(exit_tx);
+    owner.join().unwrap();
+    assert!(!ThreadBound::is_owner_alive(&bound));
+}
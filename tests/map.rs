@@ -0,0 +1,35 @@
+use std::{rc::Rc, thread};
+
+use threadporter::{BoundMap, Porter};
+
+#[test]
+fn foreign_operations_run_without_awaiting() {
+    let porter = Porter::new();
+    let map = BoundMap::new(&porter.handle());
+
+    let remote = map.clone();
+    thread::spawn(move || {
+        drop(remote.insert_with(1, || Rc::new("one")));
+        drop(remote.insert_with(2, || Rc::new("two")));
+        drop(remote.remove(2));
+    })
+    .join()
+    .unwrap();
+    porter.run_until_idle();
+
+    assert!(map.contains_key(&1));
+    assert!(!map.contains_key(&2));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn owner_operations_are_queued() {
+    let porter = Porter::new();
+    let map = BoundMap::new(&porter.handle());
+
+    drop(map.insert_with(1, || Rc::new(1)));
+    assert!(!map.contains_key(&1));
+
+    porter.run_until_idle();
+    assert!(map.contains_key(&1));
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "nightly")]
+#![feature(arbitrary_self_types)]
+
+use std::thread;
+
+use threadporter::{Lenient, ThreadBound};
+
+struct Document(String);
+
+impl Document {
+    fn title(self: &ThreadBound<Self>) -> &str {
+        &self.0
+    }
+
+    fn rename(self: &mut ThreadBound<Self>, title: &str) {
+        self.0 = title.to_string();
+    }
+
+    fn close(self: ThreadBound<Self>) -> String {
+        ThreadBound::into_inner(self).0
+    }
+
+    fn try_title(self: &ThreadBound<Self, Lenient>) -> Option<String> {
+        ThreadBound::get(self).ok().map(|doc| doc.0.clone())
+    }
+}
+
+trait Titled {
+    fn describe(&self) -> String;
+
+    fn bound_describe(self: &ThreadBound<Self>) -> String
+    where
+        Self: Sized,
+    {
+        format!("bound {}", self.describe())
+    }
+}
+
+impl Titled for Document {
+    fn describe(&self) -> String {
+        format!("document {}", self.0)
+    }
+}
+
+#[test]
+fn inherent_receivers() {
+    let mut doc = ThreadBound::new(Document("draft".to_string()));
+    assert_eq!(doc.title(), "draft");
+    doc.rename("final");
+    assert_eq!(doc.close(), "final");
+}
+
+#[test]
+fn lenient_receiver() {
+    let doc = ThreadBound::<_, Lenient>::with_policy(Document("draft".to_string()));
+    assert_eq!(doc.try_title().as_deref(), Some("draft"));
+    thread::scope(|s| s.spawn(|| assert_eq!(doc.try_title(), None)).join().unwrap());
+}
+
+#[test]
+fn sized_trait_receiver() {
+    let doc = ThreadBound::new(Document("draft".to_string()));
+    assert_eq!(doc.bound_describe(), "bound document draft");
+}
+
+#[test]
+fn trait_object_in_binding() {
+    let doc: ThreadBound<Box<dyn Titled>> = ThreadBound::new(Box::new(Document("draft".to_string())));
+    assert_eq!(doc.describe(), "document draft");
+}
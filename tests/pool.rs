@@ -0,0 +1,138 @@
+use std::{
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::pin,
+    rc::Rc,
+    sync::{
+        mpsc::{self, Sender},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread, ThreadId},
+};
+
+use threadporter::{BoundPool, Placement};
+
+struct Unpark(Thread);
+
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark()
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::park();
+    }
+}
+
+/// A `!Send` value reporting the thread it is dropped on.
+struct Tracker {
+    dropped: Sender<ThreadId>,
+    _rc: Rc<()>,
+}
+
+impl Drop for Tracker {
+    fn drop(&mut self) {
+        let _ = self.dropped.send(thread::current().id());
+    }
+}
+
+#[test]
+fn round_robin_placement() {
+    let pool = BoundPool::new(3);
+    let ids: Vec<_> = (0..6).map(|_| pool.place(|| ()).thread_id()).collect();
+
+    assert_eq!(ids[..3], ids[3..]);
+    assert_ne!(ids[0], ids[1]);
+    assert_ne!(ids[1], ids[2]);
+    assert_ne!(ids[0], ids[2]);
+}
+
+#[test]
+fn least_loaded_placement() {
+    let pool = BoundPool::with_placement(2, Placement::LeastLoaded);
+    let a = pool.place(|| ());
+    let b = pool.place(|| ());
+    let (thread_a, thread_b) = (a.thread_id(), b.thread_id());
+    assert_ne!(thread_a, thread_b);
+
+    let c = pool.place(|| ());
+    let d = pool.place(|| ());
+    assert_ne!(c.thread_id(), d.thread_id());
+
+    // Wait for the queued constructions, which keep the values alive.
+    for pooled in [&b, &c, &d] {
+        block_on(pooled.call(|()| ()));
+    }
+    drop(b);
+    if d.thread_id() == thread_b {
+        drop(d);
+    } else {
+        drop(c);
+    }
+    assert_eq!(pool.place(|| ()).thread_id(), thread_b);
+}
+
+#[test]
+fn constructed_and_used_on_host_thread() {
+    let pool = BoundPool::new(2);
+    let pooled = pool.place(|| (thread::current().id(), Rc::new(())));
+
+    let (constructed_on, used_on) = block_on(pooled.call(|(id, _)| (*id, thread::current().id())));
+    assert_eq!(constructed_on, pooled.thread_id());
+    assert_eq!(used_on, pooled.thread_id());
+}
+
+#[test]
+fn constructed_inline_when_used_on_host_before_construction() {
+    let pool = Arc::new(BoundPool::new(1));
+    let first = pool.place(|| ());
+
+    let remote = pool.clone();
+    let result = block_on(first.call(move |()| {
+        let pooled = remote.place(|| (thread::current().id(), Rc::new(())));
+        let mut call = pin!(pooled.call(|(id, _)| *id));
+        let result = call.as_mut().poll(&mut Context::from_waker(Waker::noop()));
+        drop(remote);
+        result
+    }));
+    assert_eq!(result, Poll::Ready(first.thread_id()));
+}
+
+#[test]
+fn dropped_on_host_thread() {
+    let pool = BoundPool::new(1);
+    let (tx, rx) = mpsc::channel();
+    let pooled = pool.place(move || Tracker { dropped: tx, _rc: Rc::new(()) });
+    let clone = pooled.clone();
+    let host = pooled.thread_id();
+
+    block_on(pooled.call(|_| ()));
+    drop(pooled);
+    assert!(rx.try_recv().is_err());
+    drop(clone);
+    assert_eq!(rx.recv().unwrap(), host);
+}
+
+#[test]
+fn use_after_pool_dropped() {
+    let pool = BoundPool::new(1);
+    let (tx, rx) = mpsc::channel();
+    let pooled = pool.place(move || Tracker { dropped: tx, _rc: Rc::new(()) });
+    drop(pool);
+
+    assert!(catch_unwind(AssertUnwindSafe(|| pooled.post(|_| ()))).is_err());
+    assert!(catch_unwind(AssertUnwindSafe(|| pooled.call(|_| ()))).is_err());
+
+    // The value cannot be dropped on its thread anymore, so it is leaked.
+    drop(pooled);
+    assert!(rx.try_recv().is_err());
+}
@@ -0,0 +1,35 @@
+use threadporter::{BoundHandle, BoundRegistry, StaleHandle};
+
+#[test]
+fn stale_handle_after_remove() {
+    let mut registry = BoundRegistry::new();
+    let handle = registry.insert("a");
+    assert_eq!(registry.remove(handle), Ok("a"));
+
+    let reused = registry.insert("b");
+    assert_ne!(reused, handle);
+    assert_eq!(registry.get(handle), Err(StaleHandle(handle)));
+    assert_eq!(registry.get(reused), Ok(&"b"));
+}
+
+#[test]
+fn handle_of_other_registry_is_rejected() {
+    let mut first = BoundRegistry::new();
+    let mut second = BoundRegistry::new();
+    let handle = first.insert(1);
+    second.insert(2);
+
+    assert!(!second.contains(handle));
+    assert_eq!(second.get_mut(handle), Err(StaleHandle(handle)));
+    assert_eq!(second.remove(handle), Err(StaleHandle(handle)));
+    assert_eq!(second.len(), 1);
+    assert_eq!(first.get(handle), Ok(&1));
+}
+
+#[test]
+fn raw_round_trip() {
+    let mut registry = BoundRegistry::new();
+    let handle = registry.insert(());
+    let raw = handle.into_raw();
+    assert_eq!(registry.get(BoundHandle::from_raw(raw)), Ok(&()));
+}
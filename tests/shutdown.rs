@@ -0,0 +1,154 @@
+use std::{
+    future::{pending, Future},
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use threadporter::{BoundCallback, CallError, Porter, Shutdown, ThreadBound, TryCall};
+
+fn poll_once<F: Future>(future: F) -> Poll<F::Output> {
+    pin!(future).poll(&mut Context::from_waker(Waker::noop()))
+}
+
+fn drive<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+            return output;
+        }
+    }
+}
+
+fn foreign<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+    thread::scope(|s| s.spawn(f).join().unwrap())
+}
+
+/// Records whether it was dropped and whether that happened on its owner thread.
+struct Tracker {
+    owner: thread::ThreadId,
+    dropped: Arc<AtomicBool>,
+    dropped_off_thread: Arc<AtomicBool>,
+}
+
+impl Tracker {
+    fn new() -> (Self, Arc<AtomicBool>, Arc<AtomicBool>) {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let dropped_off_thread = Arc::new(AtomicBool::new(false));
+        let tracker = Self {
+            owner: thread::current().id(),
+            dropped: dropped.clone(),
+            dropped_off_thread: dropped_off_thread.clone(),
+        };
+        (tracker, dropped, dropped_off_thread)
+    }
+}
+
+impl Drop for Tracker {
+    fn drop(&mut self) {
+        self.dropped.store(true, Ordering::SeqCst);
+        if thread::current().id() != self.owner {
+            self.dropped_off_thread.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+fn queued_call(porter: &Porter) -> TryCall<u32> {
+    let handle = porter.handle();
+    foreign(move || handle.try_call(|| 1))
+}
+
+#[test]
+fn cancel_resolves_pending_calls_as_closed() {
+    let porter = Porter::new();
+    let call = queued_call(&porter);
+
+    drive(porter.shutdown(Shutdown::Cancel));
+    assert!(matches!(poll_once(call), Poll::Ready(Err(CallError::Closed))));
+}
+
+#[test]
+fn drain_executes_pending_calls() {
+    let porter = Porter::new();
+    let call = queued_call(&porter);
+
+    drive(porter.shutdown(Shutdown::Drain));
+    assert!(matches!(poll_once(call), Poll::Ready(Ok(1))));
+}
+
+#[test]
+fn drop_resolves_pending_calls_as_closed() {
+    let porter = Porter::new();
+    let call = queued_call(&porter);
+
+    drop(porter);
+    assert!(matches!(poll_once(call), Poll::Ready(Err(CallError::Closed))));
+}
+
+#[test]
+fn calls_after_shutdown_are_rejected() {
+    let porter = Porter::new();
+    let handle = porter.handle();
+    drive(porter.shutdown(Shutdown::Drain));
+    assert!(handle.is_closed());
+
+    foreign(|| {
+        assert!(matches!(poll_once(handle.try_call(|| 1)), Poll::Ready(Err(CallError::Closed))));
+        assert!(matches!(handle.try_post(|| ()), Err(CallError::Closed)));
+        assert!(catch_unwind(AssertUnwindSafe(|| handle.post(|| ()))).is_err());
+    });
+    assert_eq!(handle.queue_len(), 0);
+}
+
+#[test]
+fn forwarded_drops_run_after_shutdown() {
+    let porter = Porter::new();
+    let (tracker, dropped, dropped_off_thread) = Tracker::new();
+    let callback = BoundCallback::new(&porter.handle(), move |()| {
+        let _ = &tracker;
+    });
+    drive(porter.shutdown(Shutdown::Drain));
+
+    foreign(move || drop(callback));
+    assert!(!dropped.load(Ordering::SeqCst));
+
+    assert_eq!(porter.run_until_idle(), 1);
+    assert!(dropped.load(Ordering::SeqCst));
+    assert!(!dropped_off_thread.load(Ordering::SeqCst));
+    assert_eq!(porter.run_until_idle(), 0);
+}
+
+#[test]
+fn forwarded_drop_after_close_leaks() {
+    let porter = Porter::new();
+    let (tracker, dropped, _) = Tracker::new();
+    let callback = BoundCallback::new(&porter.handle(), move |()| {
+        let _ = &tracker;
+    });
+    drop(porter);
+
+    foreign(move || drop(callback));
+    assert!(!dropped.load(Ordering::SeqCst));
+}
+
+#[test]
+fn dispatch_after_close_leaks() {
+    let porter = Porter::new();
+    let (tracker, dropped, _) = Tracker::new();
+    let future = ThreadBound::new(async move {
+        let _tracker = tracker;
+        pending::<()>().await
+    });
+    let future = ThreadBound::into_dispatched_future(future, &porter.handle());
+    drop(porter);
+
+    foreign(move || {
+        assert!(catch_unwind(AssertUnwindSafe(|| poll_once(future))).is_err());
+    });
+    assert!(!dropped.load(Ordering::SeqCst));
+}
@@ -0,0 +1,48 @@
+use std::{
+    future::Future,
+    pin::pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Waker},
+    thread,
+    time::Duration,
+};
+
+use threadporter::{CallError, Porter};
+
+#[test]
+fn expired_call_is_skipped() {
+    let porter = Porter::new();
+    let handle = porter.handle();
+    let executed = Arc::new(AtomicBool::new(false));
+
+    let flag = executed.clone();
+    let call = thread::scope(|s| {
+        s.spawn(|| {
+            handle.call_with_timeout(Duration::from_millis(10), move || flag.store(true, Ordering::SeqCst))
+        })
+        .join()
+        .unwrap()
+    });
+    thread::sleep(Duration::from_millis(30));
+
+    porter.run_until_idle();
+    assert!(!executed.load(Ordering::SeqCst));
+    let result = pin!(call).poll(&mut Context::from_waker(Waker::noop()));
+    assert!(matches!(result, Poll::Ready(Err(CallError::TimedOut))));
+}
+
+#[test]
+fn call_within_timeout_completes() {
+    let porter = Porter::new();
+    let handle = porter.handle();
+
+    let call =
+        thread::scope(|s| s.spawn(|| handle.call_with_timeout(Duration::from_secs(60), || 1)).join().unwrap());
+
+    porter.run_until_idle();
+    let result = pin!(call).poll(&mut Context::from_waker(Waker::noop()));
+    assert!(matches!(result, Poll::Ready(Ok(1))));
+}